@@ -0,0 +1,442 @@
+//! Resolving which library modules a solution uses, and stitching them into one file.
+//!
+//! The bundled modules go into a `mod codingame` at the end of the solution, so the solution's
+//! own `codingame::...` paths resolve to it unchanged. Inside the modules, `crate::` (and
+//! `$crate::` in macros) is pointed at `crate::codingame::`. The `#[macro_export]` macros land
+//! at the crate root, where they'd clash with a solution's `use codingame::debug;`, so they're
+//! renamed there with a `__codingame_` prefix and re-exported from `codingame` under their own
+//! names. Tests are left out: they're no use to CodinGame and count against its size limit.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::lexer::{classify, tokenize, Class, Tok, Token};
+
+/// The name solutions know the library by.
+pub const LIB_NAME: &str = "codingame";
+
+#[derive(Debug)]
+pub enum BundleError {
+    Io { path: PathBuf, error: io::Error },
+    /// `codingame::<name>` names neither a module nor an exported macro of the library.
+    UnknownItem { file: String, name: String },
+    /// The solution has a `mod x;` of its own, which would need another file.
+    FileModule { file: String, name: String },
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BundleError::Io { ref path, ref error } => write!(f, "couldn't read {}: {}", path.display(), error),
+            BundleError::UnknownItem { ref file, ref name } => {
+                write!(f, "{}: the library has no module or macro `{}`", file, name)
+            }
+            BundleError::FileModule { ref file, ref name } => {
+                write!(f, "{}: `mod {};` needs a file of its own, move it into the library", file, name)
+            }
+        }
+    }
+}
+
+impl Error for BundleError {}
+
+/// One module of the library: a `pub mod name;` of its `lib.rs`, read from `name.rs`.
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub source: String,
+    /// The `#[macro_export]` macros it defines.
+    pub macros: Vec<String>,
+}
+
+impl Module {
+    pub fn new(name: &str, source: &str) -> Self {
+        let macros = exported_macros(&tokenize(source));
+        Module { name: name.to_string(), source: source.to_string(), macros }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Library {
+    /// In `lib.rs` order.
+    pub modules: Vec<Module>,
+}
+
+impl Library {
+    /// Reads the library whose `lib.rs` is in `dir`.
+    pub fn load(dir: &Path) -> Result<Library, BundleError> {
+        let read = |path: PathBuf| fs::read_to_string(&path).map_err(|error| BundleError::Io { path, error });
+
+        let lib = read(dir.join("lib.rs"))?;
+        let modules = file_modules(&tokenize(&lib))
+            .into_iter()
+            .map(|name| read(dir.join(format!("{}.rs", name))).map(|source| Module::new(name, &source)))
+            .collect::<Result<_, _>>()?;
+        Ok(Library { modules })
+    }
+
+    // The module called, or exporting a macro called, `name`.
+    fn find(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name || m.macros.iter().any(|mac| mac == name))
+    }
+}
+
+// The names declared by `mod name;` items.
+fn file_modules<'a>(tokens: &[Token<'a>]) -> Vec<&'a str> {
+    tokens.windows(3)
+          .filter_map(|w| match (w[0].tok, w[1].tok, w[2].tok) {
+              (Tok::Ident("mod"), Tok::Ident(name), Tok::Punct(';')) => Some(name),
+              _ => None,
+          })
+          .collect()
+}
+
+// The index of the token matching the opening bracket at `open`, or the last token if there's
+//  none.
+fn matching(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.tok {
+            Tok::Punct('(') | Tok::Punct('[') | Tok::Punct('{') => depth += 1,
+            Tok::Punct(')') | Tok::Punct(']') | Tok::Punct('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => (),
+        }
+    }
+    tokens.len() - 1
+}
+
+// The macros defined by `macro_rules!` with a `#[macro_export]` among their attributes.
+fn exported_macros(tokens: &[Token]) -> Vec<String> {
+    let mut macros = Vec::new();
+    for i in 0..tokens.len().saturating_sub(2) {
+        let name = match (tokens[i].tok, tokens[i + 1].tok, tokens[i + 2].tok) {
+            (Tok::Ident("macro_rules"), Tok::Punct('!'), Tok::Ident(name)) => name,
+            _ => continue,
+        };
+
+        // walk back over the attributes
+        let mut exported = false;
+        let mut end = i;
+        while end >= 2 && tokens[end - 1].tok == Tok::Punct(']') {
+            let mut depth = 0;
+            let open = (0..end).rev().find(|&j| {
+                match tokens[j].tok {
+                    Tok::Punct(']') => depth += 1,
+                    Tok::Punct('[') => depth -= 1,
+                    _ => (),
+                }
+                depth == 0
+            });
+            match open {
+                Some(open) if open > 0 && tokens[open - 1].tok == Tok::Punct('#') => {
+                    exported |= tokens[open..end].iter().any(|t| t.tok == Tok::Ident("macro_export"));
+                    end = open - 1;
+                }
+                _ => break,
+            }
+        }
+        if exported {
+            macros.push(name.to_string());
+        }
+    }
+    macros
+}
+
+/// The first segment of each path starting `root::`, eg `input` and `skynet` for
+/// `codingame::input::Input` and `codingame::{skynet, parse_line}`, and `*` for a glob.
+pub fn path_roots<'a>(tokens: &[Token<'a>], root: &str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    for i in 0..tokens.len().saturating_sub(2) {
+        let preceded = i > 0 && tokens[i - 1].tok == Tok::PathSep;
+        if preceded || tokens[i].tok != Tok::Ident(root) || tokens[i + 1].tok != Tok::PathSep {
+            continue;
+        }
+
+        match tokens[i + 2].tok {
+            Tok::Ident(name) => names.push(name),
+            Tok::Punct('*') => names.push("*"),
+            Tok::Punct('{') => {
+                // the first identifier of each item of the group
+                let close = matching(tokens, i + 2);
+                let mut depth = 0;
+                let mut item_start = true;
+                for token in &tokens[i + 3..close] {
+                    match token.tok {
+                        Tok::Punct('{') => depth += 1,
+                        Tok::Punct('}') => depth -= 1,
+                        Tok::Punct(',') if depth == 0 => {
+                            item_start = true;
+                            continue;
+                        }
+                        Tok::Ident(name) if item_start && depth == 0 => names.push(name),
+                        Tok::Punct('*') if item_start && depth == 0 => names.push("*"),
+                        _ => (),
+                    }
+                    item_start = false;
+                }
+            }
+            _ => (),
+        }
+    }
+    names
+}
+
+fn is_test_attr(tokens: &[Token]) -> bool {
+    let toks = tokens.iter().take(7).map(|t| t.tok).collect::<Vec<_>>();
+    toks.starts_with(&[Tok::Punct('#'), Tok::Punct('['), Tok::Ident("test"), Tok::Punct(']')])
+        || toks.starts_with(&[Tok::Punct('#'), Tok::Punct('['), Tok::Ident("cfg"), Tok::Punct('('),
+                              Tok::Ident("test"), Tok::Punct(')'), Tok::Punct(']')])
+}
+
+// The byte range of the first `#[test]` or `#[cfg(test)]` item, from the start of its first
+//  line (taking in any comments and attributes right above) to the end of its last.
+fn next_test_item(src: &str) -> Option<(usize, usize)> {
+    let tokens = tokenize(src);
+    let attr = (0..tokens.len()).find(|&i| is_test_attr(&tokens[i..]))?;
+
+    // the item runs to a `;` or a closing brace at the top level, whichever comes first
+    let mut i = matching(&tokens, attr + 1) + 1;
+    while i < tokens.len() {
+        match tokens[i].tok {
+            Tok::Punct(';') => break,
+            Tok::Punct('{') => {
+                i = matching(&tokens, i);
+                if tokens.get(i + 1).map(|t| t.tok) == Some(Tok::Punct(';')) {
+                    i += 1;
+                }
+                break;
+            }
+            Tok::Punct('(') | Tok::Punct('[') => i = matching(&tokens, i) + 1,
+            _ => i += 1,
+        }
+    }
+    let end_byte = tokens.get(i).map_or(src.len(), |t| t.end);
+
+    let line_start = |pos: usize| src[..pos].rfind('\n').map_or(0, |n| n + 1);
+    let mut start = line_start(tokens[attr].start);
+    while start > 0 {
+        let above = line_start(start - 1);
+        let line = src[above..start].trim();
+        let attached = line.starts_with("#[") || (line.starts_with("//") && !line.starts_with("//!"));
+        if !attached {
+            break;
+        }
+        start = above;
+    }
+
+    let mut end = src[end_byte..].find('\n').map_or(src.len(), |n| end_byte + n + 1);
+    // and one of the blank lines around it, if it had them on both sides
+    let blank_above = start == 0 || src[..start - 1].ends_with('\n');
+    let blank_below = src[end..].starts_with('\n');
+    if blank_above && blank_below {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+/// `src` without its `#[test]` and `#[cfg(test)]` items.
+pub fn strip_tests(src: &str) -> String {
+    let mut src = src.to_string();
+    while let Some((start, end)) = next_test_item(&src) {
+        src.replace_range(start..end, "");
+    }
+    src
+}
+
+/// The name an exported macro of the library gets at the bundle's crate root.
+pub fn root_macro_name(name: &str) -> String {
+    format!("__{}_{}", LIB_NAME, name)
+}
+
+/// Renames the definitions of `macros` in `src`, and the uses of them not reached through a
+/// path, to their `root_macro_name`s.
+pub fn rename_macros(src: &str, macros: &[String]) -> String {
+    let tokens = tokenize(src);
+    let mut out = src.to_string();
+    for i in (0..tokens.len().saturating_sub(1)).rev() {
+        let name = match tokens[i].tok {
+            Tok::Ident(name) if macros.iter().any(|m| m == name) => name,
+            _ => continue,
+        };
+        let preceded = i > 0 && tokens[i - 1].tok == Tok::PathSep;
+        let defined = i >= 2 && tokens[i - 2].tok == Tok::Ident("macro_rules") && tokens[i - 1].tok == Tok::Punct('!');
+        let invoked = !preceded && tokens[i + 1].tok == Tok::Punct('!');
+        if defined || invoked {
+            out.replace_range(tokens[i].start..tokens[i].end, &root_macro_name(name));
+        }
+    }
+    out
+}
+
+/// Points the `crate::` and `$crate::` paths in `src` into the `codingame` module.
+pub fn relocate_crate_paths(src: &str) -> String {
+    let tokens = tokenize(src);
+    let mut out = src.to_string();
+    for i in (0..tokens.len().saturating_sub(1)).rev() {
+        let preceded = i > 0 && tokens[i - 1].tok == Tok::PathSep;
+        if !preceded && tokens[i].tok == Tok::Ident("crate") && tokens[i + 1].tok == Tok::PathSep {
+            out.insert_str(tokens[i + 1].end, &format!("{}::", LIB_NAME));
+        }
+    }
+    out
+}
+
+/// `src` with each line that starts in code indented by `indent`. Lines starting inside a string
+/// literal are left alone, since indenting them would change the string.
+pub fn indent(src: &str, indent: &str) -> String {
+    let classes = classify(src);
+    let mut out = String::new();
+    let mut pos = 0;
+    for line in src.split_inclusive('\n') {
+        if classes.get(pos) != Some(&Class::Literal) && !line.trim().is_empty() {
+            out.push_str(indent);
+        }
+        out.push_str(line);
+        pos += line.len();
+    }
+    out
+}
+
+/// The modules of `lib` that `src` uses, directly or through other modules, in `lib.rs` order.
+pub fn used_modules<'a>(file: &str, src: &str, lib: &'a Library) -> Result<Vec<&'a Module>, BundleError> {
+    let mut used = vec![false; lib.modules.len()];
+    let mut queue = vec![(file.to_string(), src.to_string(), LIB_NAME)];
+
+    while let Some((file, src, root)) = queue.pop() {
+        for name in path_roots(&tokenize(&src), root) {
+            let found = if name == "*" { 0..lib.modules.len() } else {
+                let index = lib.find(name).ok_or_else(|| {
+                    BundleError::UnknownItem { file: file.clone(), name: name.to_string() }
+                })?;
+                index..index + 1
+            };
+            for index in found {
+                if !used[index] {
+                    used[index] = true;
+                    let module = &lib.modules[index];
+                    queue.push((format!("{}.rs", module.name), strip_tests(&module.source), "crate"));
+                }
+            }
+        }
+    }
+
+    Ok(lib.modules.iter().zip(used).filter(|&(_, used)| used).map(|(m, _)| m).collect())
+}
+
+/// The solution `src`, read from `file`, with the modules of `lib` it uses appended.
+pub fn bundle(file: &str, src: &str, lib: &Library) -> Result<String, BundleError> {
+    let src = strip_tests(src);
+    if let Some(name) = file_modules(&tokenize(&src)).first() {
+        return Err(BundleError::FileModule { file: file.to_string(), name: name.to_string() });
+    }
+
+    let modules = used_modules(file, &src, lib)?;
+    let names = modules.iter().map(|m| m.name.as_str()).collect::<Vec<_>>();
+    let mut out = format!("// {} bundled with {}::{{{}}}. Edit the sources, not this file.\n\n",
+                          file, LIB_NAME, names.join(", "));
+    out.push_str(src.trim_end());
+    out.push('\n');
+    if modules.is_empty() {
+        return Ok(out);
+    }
+
+    out.push_str(&format!("\n#[allow(dead_code, unused_imports, unused_macros)]\nmod {} {{\n", LIB_NAME));
+    let macros = modules.iter()
+        .flat_map(|m| m.macros.iter().map(|name| format!("{} as {}", root_macro_name(name), name)))
+        .collect::<Vec<_>>();
+    if !macros.is_empty() {
+        out.push_str(&format!("    pub use crate::{{{}}};\n", macros.join(", ")));
+    }
+    for module in modules {
+        let source = relocate_crate_paths(&rename_macros(&strip_tests(&module.source), &module.macros));
+        out.push_str(&format!("\n    pub mod {} {{\n", module.name));
+        out.push_str(&indent(source.trim_end(), "        "));
+        out.push_str("\n    }\n");
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> Library {
+        Library {
+            modules: vec![
+                Module::new("input", "//! Reading.\n\npub fn read() -> i32 { 4 }\n\n/// Reads a line.\n\
+                                      #[macro_export]\nmacro_rules! read_line {\n    () => { $crate::input::read() };\n}\n\n\
+                                      #[cfg(test)]\nmod tests {\n    #[test]\n    fn test_read() {}\n}\n"),
+                Module::new("grid", "use crate::input;\n\npub fn width() -> i32 { input::read() }\n"),
+                Module::new("unused", "pub fn nothing() {}\n"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_path_roots() {
+        let tokens = tokenize("use codingame::grid::width;\nuse codingame::{input::{self, read}, read_line};\n\
+                               fn f() { codingame::unused::nothing(); x::codingame::y(); }");
+        assert_eq!(path_roots(&tokens, "codingame"), vec!["grid", "input", "read_line", "unused"]);
+        assert_eq!(path_roots(&tokenize("use crate::*;"), "crate"), vec!["*"]);
+        assert_eq!(exported_macros(&tokenize(&library().modules[0].source)), vec!["read_line"]);
+    }
+
+    #[test]
+    fn test_strip_tests() {
+        let src = "fn main() {}\n\n// helper\n#[cfg(test)]\nconst LEVEL: &str = \"{\n\";\n\n\
+                   #[test]\n#[should_panic]\nfn test_main() { if true { main(); } }\n\nfn after() {}\n";
+        assert_eq!(strip_tests(src), "fn main() {}\n\nfn after() {}\n");
+        assert_eq!(strip_tests("#[cfg(test)]\nuse x::Y;\nfn f() {}\n"), "fn f() {}\n");
+    }
+
+    #[test]
+    fn test_relocate_and_indent() {
+        let src = "use crate::input;\nmacro_rules! m { () => { $crate::read!() } }\npub(crate) fn f() {}\n\
+                   // crate::x\nconst S: &str = \"a\ncrate::b\";\n";
+        assert_eq!(relocate_crate_paths(src),
+                   "use crate::codingame::input;\nmacro_rules! m { () => { $crate::codingame::read!() } }\n\
+                    pub(crate) fn f() {}\n// crate::x\nconst S: &str = \"a\ncrate::b\";\n");
+        assert_eq!(indent("a\n\nb = \"x\ny\";\n", "  "), "  a\n\n  b = \"x\ny\";\n");
+    }
+
+    #[test]
+    fn test_rename_macros() {
+        let src = "macro_rules! log { () => {} }\nmacro_rules! info { () => { $crate::log!() } }\n\
+                   fn f() { log!(); crate::info!(); let log = 1; }\n";
+        assert_eq!(rename_macros(src, &["log".to_string(), "info".to_string()]),
+                   "macro_rules! __codingame_log { () => {} }\n\
+                    macro_rules! __codingame_info { () => { $crate::log!() } }\n\
+                    fn f() { __codingame_log!(); crate::info!(); let log = 1; }\n");
+    }
+
+    #[test]
+    fn test_bundle() {
+        let lib = library();
+        let src = "use codingame::grid;\n\nfn main() { println!(\"{}\", grid::width()); }\n\n\
+                   #[test]\nfn test_main() { codingame::unused::nothing(); }\n";
+        let out = bundle("main.rs", src, &lib).unwrap();
+        assert!(out.starts_with("// main.rs bundled with codingame::{input, grid}."));
+        assert!(out.contains("    pub use crate::{__codingame_read_line as read_line};\n"));
+        assert!(out.contains("macro_rules! __codingame_read_line {"));
+        assert!(out.contains("\n    pub mod input {\n        //! Reading.\n"));
+        assert!(out.contains("        use crate::codingame::input;\n"));
+        assert!(out.contains("$crate::codingame::input::read()"));
+        assert!(!out.contains("mod unused") && !out.contains("test_"));
+
+        let err = bundle("main.rs", "use codingame::nope::X;\n", &lib).unwrap_err();
+        assert_eq!(err.to_string(), "main.rs: the library has no module or macro `nope`");
+        let err = bundle("main.rs", "mod helpers;\n", &lib).unwrap_err();
+        assert_eq!(err.to_string(), "main.rs: `mod helpers;` needs a file of its own, move it into the library");
+        assert_eq!(bundle("main.rs", "fn main() {}\n", &lib).unwrap(),
+                   "// main.rs bundled with codingame::{}. Edit the sources, not this file.\n\nfn main() {}\n");
+    }
+}
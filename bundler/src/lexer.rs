@@ -0,0 +1,204 @@
+//! Just enough of a Rust lexer to tell code from comments and literals, and to split the code
+//! into identifiers and punctuation.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tok<'a> {
+    Ident(&'a str),
+    /// `::`
+    PathSep,
+    Punct(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub tok: Tok<'a>,
+    /// Byte range of the token in the source.
+    pub start: usize,
+    pub end: usize,
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b == b'_' || b.is_ascii_alphanumeric()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Code,
+    Comment,
+    /// A string or char literal.
+    Literal,
+}
+
+/// For each byte of `src`, whether it's code rather than part of a comment or of a string or
+/// char literal.
+pub fn code_mask(src: &str) -> Vec<bool> {
+    classify(src).into_iter().map(|class| class == Class::Code).collect()
+}
+
+/// What each byte of `src` is part of.
+pub fn classify(src: &str) -> Vec<Class> {
+    let bytes = src.as_bytes();
+    let mut classes = vec![Class::Code; bytes.len()];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        let (class, end) = if rest.starts_with(b"//") {
+            (Class::Comment, rest.iter().position(|&b| b == b'\n').map_or(bytes.len(), |n| i + n))
+        } else if rest.starts_with(b"/*") {
+            // block comments nest
+            let mut depth = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                if bytes[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if bytes[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            (Class::Comment, j)
+        } else if bytes[i] == b'"' {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != b'"' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            (Class::Literal, (j + 1).min(bytes.len()))
+        } else if let Some(end) = raw_string_end(bytes, i) {
+            (Class::Literal, end)
+        } else if bytes[i] == b'\'' {
+            match char_literal_end(src, i) {
+                Some(end) => (Class::Literal, end),
+                // a lifetime or a label
+                None => {
+                    i += 1;
+                    continue;
+                }
+            }
+        } else {
+            i += 1;
+            continue;
+        };
+
+        for c in &mut classes[i..end] {
+            *c = class;
+        }
+        i = end;
+    }
+
+    classes
+}
+
+// The end of the raw string `r#"..."#` (or `br"..."`) starting at `i`, if one does.
+fn raw_string_end(bytes: &[u8], i: usize) -> Option<usize> {
+    if bytes[i] != b'r' {
+        return None;
+    }
+    let prefix_start = if i > 0 && bytes[i - 1] == b'b' { i - 1 } else { i };
+    if prefix_start > 0 && is_ident_byte(bytes[prefix_start - 1]) {
+        return None;
+    }
+
+    let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+    let open = i + 1 + hashes;
+    if bytes.get(open) != Some(&b'"') {
+        return None;
+    }
+
+    let mut j = open + 1;
+    while j < bytes.len() {
+        if bytes[j] == b'"' && bytes[j + 1..].iter().take(hashes).filter(|&&b| b == b'#').count() == hashes {
+            return Some(j + 1 + hashes);
+        }
+        j += 1;
+    }
+    Some(bytes.len())
+}
+
+// The end of the char literal starting at `i`, or `None` if the quote starts a lifetime.
+fn char_literal_end(src: &str, i: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    if bytes.get(i + 1) == Some(&b'\\') {
+        let close = bytes[i + 3..].iter().position(|&b| b == b'\'')?;
+        return Some(i + 3 + close + 1);
+    }
+    let c = src[i + 1..].chars().next()?;
+    let close = i + 1 + c.len_utf8();
+    if bytes.get(close) == Some(&b'\'') {
+        Some(close + 1)
+    } else {
+        None
+    }
+}
+
+/// The identifiers and punctuation of the code in `src`. Literals and comments are skipped.
+pub fn tokenize(src: &str) -> Vec<Token<'_>> {
+    let bytes = src.as_bytes();
+    let mask = code_mask(src);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !mask[i] || b.is_ascii_whitespace() || !b.is_ascii() {
+            i += 1;
+        } else if b.is_ascii_digit() {
+            // numbers, including suffixes like the `u8` of `1u8`
+            while i < bytes.len() && mask[i] && is_ident_byte(bytes[i]) {
+                i += 1;
+            }
+        } else if is_ident_byte(b) {
+            let start = i;
+            while i < bytes.len() && mask[i] && is_ident_byte(bytes[i]) {
+                i += 1;
+            }
+            tokens.push(Token { tok: Tok::Ident(&src[start..i]), start, end: i });
+        } else if bytes[i..].starts_with(b"::") {
+            tokens.push(Token { tok: Tok::PathSep, start: i, end: i + 2 });
+            i += 2;
+        } else {
+            tokens.push(Token { tok: Tok::Punct(b as char), start: i, end: i + 1 });
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(src: &str) -> String {
+        src.chars().zip(code_mask(src)).map(|(c, m)| if m { c } else { '_' }).collect()
+    }
+
+    #[test]
+    fn test_code_mask() {
+        assert_eq!(code("a // b\nc"), "a ____\nc");
+        assert_eq!(code("a /* b /* c */ d */ e"), "a _________________ e");
+        assert_eq!(code(r#"f("a\"b", 'c', '\'')"#), "f(______, ___, ____)");
+        assert_eq!(code(r###"x(r#"a"b"#, br"c")"###), "x(________, b____)");
+        assert_eq!(code("fn f<'a>(x: &'a str) -> char { 'x' }"), "fn f<'a>(x: &'a str) -> char { ___ }");
+        assert_eq!(code("'outer: loop { break 'outer; }"), "'outer: loop { break 'outer; }");
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let toks = tokenize("use crate::input::{Input, parse_line}; // crate::skynet\nlet s = \"crate::x\";")
+            .into_iter()
+            .map(|t| t.tok)
+            .collect::<Vec<_>>();
+        assert_eq!(toks[..6], [Tok::Ident("use"), Tok::Ident("crate"), Tok::PathSep, Tok::Ident("input"),
+                               Tok::PathSep, Tok::Punct('{')]);
+        assert!(!toks.contains(&Tok::Ident("skynet")));
+        assert!(!toks.contains(&Tok::Ident("x")));
+        assert_eq!(tokenize("1u8 + x")[1].tok, Tok::Ident("x"));
+    }
+}
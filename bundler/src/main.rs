@@ -0,0 +1,58 @@
+//! Bundles a solution and the `codingame` modules it uses into a single file for submission.
+//!
+//!     bundler <solution.rs> [--lib <dir>] [-o <out.rs>]
+//!
+//! `--lib` is the `src` directory of the library, by default the `codingame` crate next to this
+//! one. Without `-o` the bundle goes to stdout.
+
+mod bundle;
+mod lexer;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use bundle::{bundle, BundleError, Library};
+
+fn usage() -> ! {
+    writeln!(io::stderr(), "usage: bundler <solution.rs> [--lib <dir>] [-o <out.rs>]").ok();
+    process::exit(2);
+}
+
+fn run(entry: &Path, lib: &Path, out: Option<&Path>) -> Result<(), BundleError> {
+    let src = fs::read_to_string(entry).map_err(|error| BundleError::Io { path: entry.to_path_buf(), error })?;
+    let file = entry.file_name().map_or_else(|| entry.display().to_string(), |f| f.to_string_lossy().into_owned());
+    let bundled = bundle(&file, &src, &Library::load(lib)?)?;
+
+    match out {
+        Some(path) => fs::write(path, bundled).map_err(|error| BundleError::Io { path: path.to_path_buf(), error }),
+        None => {
+            io::stdout().write_all(bundled.as_bytes()).ok();
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    let mut entry = None;
+    let mut lib = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../codingame/src"));
+    let mut out = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lib" => lib = PathBuf::from(args.next().unwrap_or_else(|| usage())),
+            "-o" => out = Some(PathBuf::from(args.next().unwrap_or_else(|| usage()))),
+            _ if entry.is_none() && !arg.starts_with('-') => entry = Some(PathBuf::from(arg)),
+            _ => usage(),
+        }
+    }
+    let entry = entry.unwrap_or_else(|| usage());
+
+    if let Err(e) = run(&entry, &lib, out.as_deref()) {
+        writeln!(io::stderr(), "bundler: {}", e).ok();
+        process::exit(1);
+    }
+}
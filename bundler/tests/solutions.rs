@@ -0,0 +1,70 @@
+// Bundles the Rust solutions with the real library and checks that what comes out builds on
+//  its own, as it has to on CodinGame.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const SOLUTIONS: &[&str] = &[
+    "ascii_art",
+    "game_of_life",
+    "monday_tuesday_happy_days",
+    "power_of_thor_ep1",
+    "power_of_thor_ep2",
+    "skynet_revolution_ep1",
+    "skynet_revolution_ep2",
+    "there_is_no_spoon_ep1",
+    "there_is_no_spoon_ep2",
+];
+
+fn bin_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/bin")
+}
+
+// Bundles `solution` and compiles the bundle, returning the path of the executable.
+fn build(solution: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("bundles");
+    fs::create_dir_all(&dir).unwrap();
+    let bundled = dir.join(format!("{}.rs", solution));
+    let exe = dir.join(solution);
+
+    let status = Command::new(env!("CARGO_BIN_EXE_bundler"))
+        .arg(bin_dir().join(format!("{}.rs", solution)))
+        .arg("-o").arg(&bundled)
+        .status()
+        .unwrap();
+    assert!(status.success(), "bundling {} failed", solution);
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "--cap-lints", "allow", "-o"])
+        .arg(&exe)
+        .arg(&bundled)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "bundle of {} doesn't build:\n{}",
+            solution, String::from_utf8_lossy(&output.stderr));
+    exe
+}
+
+#[test]
+fn test_bundles_build() {
+    for solution in SOLUTIONS {
+        build(solution);
+    }
+}
+
+#[test]
+fn test_bundle_plays() {
+    let exe = build("skynet_revolution_ep1");
+    let mut child = Command::new(exe)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"3 2 1\n1 2\n1 0\n2\n1\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2 1\n");
+}
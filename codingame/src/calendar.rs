@@ -0,0 +1,552 @@
+//! Months, weekdays and dates, for date puzzles like Monday Tuesday Happy Days. Names come
+//! from a `Locale`, `ENGLISH` unless asked otherwise, and are written CodinGame's way:
+//! abbreviated months and full weekdays. They're read in any case, in full or abbreviated.
+//!
+//! A `Date` is a day of some year that may or may not be a leap year, as the puzzle gives
+//! them. A `YearDate` says which year, under the Gregorian rules carried back before 1582, and
+//! can be turned into a count of days so that spans across years are plain subtraction.
+//!
+//! Weekdays are worked out with modular arithmetic on those day numbers, so counting
+//! backwards is the same sum as counting forwards, just with a negative number of days.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Month {
+    Jan,
+    Feb,
+    Mar,
+    Apr,
+    May,
+    Jun,
+    Jul,
+    Aug,
+    Sep,
+    Oct,
+    Nov,
+    Dec,
+}
+
+impl Month {
+    pub const ALL: [Month; 12] = [Month::Jan, Month::Feb, Month::Mar, Month::Apr, Month::May, Month::Jun,
+                                  Month::Jul, Month::Aug, Month::Sep, Month::Oct, Month::Nov, Month::Dec];
+
+    /// How many days the month has, in a leap year or not.
+    pub fn length(self, leap: bool) -> u32 {
+        match self {
+            Month::Feb if leap => 29,
+            Month::Feb => 28,
+            Month::Apr | Month::Jun | Month::Sep | Month::Nov => 30,
+            _ => 31,
+        }
+    }
+
+    // The days in the year before this month starts.
+    fn days_before(self, leap: bool) -> u32 {
+        Month::ALL.iter().take_while(|&&month| month != self).map(|month| month.length(leap)).sum()
+    }
+}
+
+/// The English abbreviation, eg `Mar`; `Locale::month_name` has the others.
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(ENGLISH.month_name(*self))
+    }
+}
+
+impl FromStr for Month {
+    type Err = CalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ENGLISH.parse_month(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+                                   Weekday::Friday, Weekday::Saturday, Weekday::Sunday];
+}
+
+/// The English name, eg `Monday`; `Locale::weekday_name` has the others.
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(ENGLISH.weekday_name(*self))
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = CalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ENGLISH.parse_weekday(s)
+    }
+}
+
+/// The names of months and weekdays in some language, in full and abbreviated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub months: [&'static str; 12],
+    pub short_months: [&'static str; 12],
+    pub weekdays: [&'static str; 7],
+    pub short_weekdays: [&'static str; 7],
+}
+
+/// CodinGame's names, and the default.
+pub const ENGLISH: Locale = Locale {
+    months: ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+             "November", "December"],
+    short_months: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+    short_weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+};
+
+pub const FRENCH: Locale = Locale {
+    months: ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre",
+             "novembre", "décembre"],
+    short_months: ["janv", "févr", "mars", "avr", "mai", "juin", "juil", "août", "sept", "oct", "nov", "déc"],
+    weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+    short_weekdays: ["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+};
+
+pub const GERMAN: Locale = Locale {
+    months: ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober",
+             "November", "Dezember"],
+    short_months: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+    weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+    short_weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+};
+
+pub const SPANISH: Locale = Locale {
+    months: ["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre",
+             "noviembre", "diciembre"],
+    short_months: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"],
+    weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+    short_weekdays: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+};
+
+// Whether `text` is `name`, ignoring case and surrounding space.
+fn same_name(name: &str, text: &str) -> bool {
+    name.to_lowercase() == text.trim().to_lowercase()
+}
+
+impl Locale {
+    /// A built-in locale by its language code: `en`, `fr`, `de` or `es`.
+    pub fn builtin(code: &str) -> Option<Locale> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(ENGLISH),
+            "fr" => Some(FRENCH),
+            "de" => Some(GERMAN),
+            "es" => Some(SPANISH),
+            _ => None,
+        }
+    }
+
+    /// The month's abbreviated name, as a date is written.
+    pub fn month_name(&self, month: Month) -> &'static str {
+        self.short_months[month as usize]
+    }
+
+    /// The weekday's full name, as the answer is written.
+    pub fn weekday_name(&self, weekday: Weekday) -> &'static str {
+        self.weekdays[weekday as usize]
+    }
+
+    /// The month named by `s`, in full or abbreviated.
+    pub fn parse_month(&self, s: &str) -> Result<Month, CalendarError> {
+        Month::ALL.iter()
+                  .find(|&&month| {
+                      same_name(self.months[month as usize], s) || same_name(self.short_months[month as usize], s)
+                  })
+                  .copied()
+                  .ok_or_else(|| CalendarError::UnknownMonth { text: s.to_string() })
+    }
+
+    /// The weekday named by `s`, in full or abbreviated.
+    pub fn parse_weekday(&self, s: &str) -> Result<Weekday, CalendarError> {
+        Weekday::ALL.iter()
+                    .find(|&&day| {
+                        same_name(self.weekdays[day as usize], s) || same_name(self.short_weekdays[day as usize], s)
+                    })
+                    .copied()
+                    .ok_or_else(|| CalendarError::UnknownWeekday { text: s.to_string() })
+    }
+
+    /// `date` as this locale writes it, eg `Mar 12`.
+    pub fn date(&self, date: Date) -> String {
+        format!("{} {}", self.month_name(date.month), date.day)
+    }
+
+    /// `date` as this locale writes it, eg `Mar 12 2024`.
+    pub fn year_date(&self, date: YearDate) -> String {
+        format!("{} {}", self.date(date.date()), date.year)
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        ENGLISH
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    UnknownMonth { text: String },
+    UnknownWeekday { text: String },
+    /// A day of the month that's zero or past the month's end.
+    BadDay { month: Month, day: u32, leap: bool },
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CalendarError::UnknownMonth { ref text } => write!(f, "unknown month '{}'", text),
+            CalendarError::UnknownWeekday { ref text } => write!(f, "unknown weekday '{}'", text),
+            CalendarError::BadDay { month, day, leap } => {
+                write!(f, "{} has no day {} in a {}", month, day, if leap { "leap year" } else { "common year" })
+            }
+        }
+    }
+}
+
+impl Error for CalendarError {}
+
+/// A day of some year, which doesn't say whether it's a leap year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub month: Month,
+    /// The day of the month, from 1.
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(month: Month, day: u32) -> Self {
+        Date { month, day }
+    }
+
+    /// The date, if the month has that day in a leap year or not.
+    pub fn checked(month: Month, day: u32, leap: bool) -> Result<Self, CalendarError> {
+        if (1..=month.length(leap)).contains(&day) {
+            Ok(Date::new(month, day))
+        } else {
+            Err(CalendarError::BadDay { month, day, leap })
+        }
+    }
+
+    /// The day's number in the year, from 1 for the 1st of January.
+    pub fn day_of_year(self, leap: bool) -> u32 {
+        self.month.days_before(leap) + self.day
+    }
+
+    /// The date that's day `n` of the year, counting from 1, if the year has that many days.
+    pub fn from_day_of_year(n: u32, leap: bool) -> Option<Date> {
+        let mut rest = n.checked_sub(1)?;
+        for &month in &Month::ALL {
+            if rest < month.length(leap) {
+                return Some(Date::new(month, rest + 1));
+            }
+            rest -= month.length(leap);
+        }
+        None
+    }
+}
+
+/// In English, eg `Mar 12`; `Locale::date` writes the others.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.month, self.day)
+    }
+}
+
+/// Whether `year` has a 29th of February: every fourth year, except centuries that don't
+/// divide by 400.
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// Days from the 1st of January of year 1 to that of `year`.
+fn days_before_year(year: i32) -> i64 {
+    let past = year as i64 - 1;
+    365 * past + past.div_euclid(4) - past.div_euclid(100) + past.div_euclid(400)
+}
+
+/// A day of a particular year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearDate {
+    pub year: i32,
+    pub month: Month,
+    /// The day of the month, from 1.
+    pub day: u32,
+}
+
+impl YearDate {
+    pub fn new(year: i32, month: Month, day: u32) -> Self {
+        YearDate { year, month, day }
+    }
+
+    /// The date, if the month has that day in that year.
+    pub fn checked(year: i32, month: Month, day: u32) -> Result<Self, CalendarError> {
+        Date::checked(month, day, is_leap_year(year)).map(|date| YearDate::new(year, date.month, date.day))
+    }
+
+    /// The day and month, without the year.
+    pub fn date(self) -> Date {
+        Date::new(self.month, self.day)
+    }
+
+    /// How many days this is after the 1st of January of year 1, negative before it.
+    pub fn days(self) -> i64 {
+        days_before_year(self.year) + self.date().day_of_year(is_leap_year(self.year)) as i64 - 1
+    }
+
+    /// The date `days` days after the 1st of January of year 1.
+    pub fn from_days(days: i64) -> YearDate {
+        // 146097 days in every 400 years gets within a year either way
+        let mut year = (days * 400).div_euclid(146_097) as i32 + 1;
+        while days_before_year(year) > days {
+            year -= 1;
+        }
+        while days_before_year(year + 1) <= days {
+            year += 1;
+        }
+        let date = Date::from_day_of_year((days - days_before_year(year)) as u32 + 1, is_leap_year(year)).unwrap();
+        YearDate::new(year, date.month, date.day)
+    }
+
+    /// The date `n_days` later, or earlier if `n_days` is negative.
+    pub fn add_days(self, n_days: i64) -> YearDate {
+        YearDate::from_days(self.days() + n_days)
+    }
+
+    /// How many days it is from this date to `other`, negative if `other` comes first.
+    pub fn days_until(self, other: YearDate) -> i64 {
+        other.days() - self.days()
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // the 1st of January of year 1 was a Monday
+        weekday_after(Weekday::Monday, self.days())
+    }
+}
+
+/// In English, eg `Mar 12 2024`; `Locale::year_date` writes the others.
+impl fmt::Display for YearDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.month, self.day, self.year)
+    }
+}
+
+/// The weekday `n_days` after `weekday`, or before it if `n_days` is negative.
+pub fn weekday_after(weekday: Weekday, n_days: i64) -> Weekday {
+    Weekday::ALL[(weekday as i64 + n_days).rem_euclid(7) as usize]
+}
+
+/// The weekday of `date`, given that `known` fell on `known_weekday` in the same year.
+pub fn weekday_on(date: Date, known: Date, known_weekday: Weekday, leap: bool) -> Weekday {
+    let n_days = date.day_of_year(leap) as i64 - known.day_of_year(leap) as i64;
+    weekday_after(known_weekday, n_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The day after `date`, worked out from month lengths alone.
+    fn next_day(date: Date, leap: bool) -> Option<Date> {
+        if date.day < date.month.length(leap) {
+            return Some(Date::new(date.month, date.day + 1));
+        }
+        Month::ALL.get(date.month as usize + 1).map(|&month| Date::new(month, 1))
+    }
+
+    // The day before `date`, likewise.
+    fn previous_day(date: Date, leap: bool) -> Option<Date> {
+        if date.day > 1 {
+            return Some(Date::new(date.month, date.day - 1));
+        }
+        let month = *Month::ALL.get((date.month as usize).checked_sub(1)?)?;
+        Some(Date::new(month, month.length(leap)))
+    }
+
+    // Every date of the year with its weekday, found by stepping out a day at a time both ways
+    //  from `known`.
+    fn weekdays_by_stepping(known: Date, known_weekday: Weekday, leap: bool) -> Vec<(Date, Weekday)> {
+        let step = |day: Weekday, forward: bool| {
+            let i = Weekday::ALL.iter().position(|&d| d == day).unwrap();
+            Weekday::ALL[if forward { (i + 1) % 7 } else { (i + 6) % 7 }]
+        };
+        let mut dates = vec![(known, known_weekday)];
+        let (mut date, mut day) = (known, known_weekday);
+        while let Some(before) = previous_day(date, leap) {
+            day = step(day, false);
+            date = before;
+            dates.push((date, day));
+        }
+        let (mut date, mut day) = (known, known_weekday);
+        while let Some(after) = next_day(date, leap) {
+            day = step(day, true);
+            date = after;
+            dates.push((date, day));
+        }
+        dates
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!("Sep".parse::<Month>(), Ok(Month::Sep));
+        assert_eq!("Wednesday".parse::<Weekday>(), Ok(Weekday::Wednesday));
+        assert_eq!("Sept".parse::<Month>().unwrap_err().to_string(), "unknown month 'Sept'");
+        assert_eq!("Wednes".parse::<Weekday>().unwrap_err().to_string(), "unknown weekday 'Wednes'");
+        assert_eq!(Date::new(Month::Mar, 12).to_string(), "Mar 12");
+
+        for text in &["Feb", "feb", "FEBRUARY", " February"] {
+            assert_eq!(text.parse::<Month>(), Ok(Month::Feb), "{:?}", text);
+        }
+        for text in &["Thu", "thursday", "THURSDAY"] {
+            assert_eq!(text.parse::<Weekday>(), Ok(Weekday::Thursday), "{:?}", text);
+        }
+        assert_eq!("".parse::<Month>(), Err(CalendarError::UnknownMonth { text: String::new() }));
+    }
+
+    #[test]
+    fn test_locales() {
+        assert_eq!(Locale::default(), ENGLISH);
+        assert_eq!(Locale::builtin("DE"), Some(GERMAN));
+        assert_eq!(Locale::builtin("it"), None);
+
+        for locale in &[ENGLISH, FRENCH, GERMAN, SPANISH] {
+            for &month in &Month::ALL {
+                assert_eq!(locale.parse_month(locale.month_name(month)), Ok(month));
+                assert_eq!(locale.parse_month(&locale.months[month as usize].to_uppercase()), Ok(month));
+            }
+            for &day in &Weekday::ALL {
+                assert_eq!(locale.parse_weekday(locale.weekday_name(day)), Ok(day));
+                assert_eq!(locale.parse_weekday(locale.short_weekdays[day as usize]), Ok(day));
+            }
+        }
+
+        assert_eq!(FRENCH.parse_month("AOÛT"), Ok(Month::Aug));
+        assert_eq!(GERMAN.parse_month("märz"), Ok(Month::Mar));
+        assert_eq!(SPANISH.parse_weekday("Miércoles"), Ok(Weekday::Wednesday));
+        assert_eq!(FRENCH.parse_weekday("Monday"), Err(CalendarError::UnknownWeekday { text: "Monday".to_string() }));
+        assert_eq!(GERMAN.date(Date::new(Month::Oct, 3)), "Okt 3");
+        assert_eq!(FRENCH.year_date(YearDate::new(2024, Month::Feb, 29)), "févr 29 2024");
+        assert_eq!(ENGLISH.year_date(YearDate::new(2024, Month::Feb, 29)), YearDate::new(2024, Month::Feb, 29).to_string());
+        assert_eq!(SPANISH.weekday_name(Weekday::Saturday), "sábado");
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(Date::checked(Month::Feb, 29, true), Ok(Date::new(Month::Feb, 29)));
+        assert_eq!(Date::checked(Month::Feb, 29, false).unwrap_err().to_string(), "Feb has no day 29 in a common year");
+        assert_eq!(Date::checked(Month::Apr, 31, true), Err(CalendarError::BadDay { month: Month::Apr, day: 31, leap: true }));
+        assert!(Date::checked(Month::Dec, 0, false).is_err());
+        assert!(YearDate::checked(2000, Month::Feb, 29).is_ok());
+        assert!(YearDate::checked(1900, Month::Feb, 29).is_err());
+    }
+
+    #[test]
+    fn test_day_of_year() {
+        assert_eq!(Month::ALL.iter().map(|m| m.length(false)).sum::<u32>(), 365);
+        assert_eq!(Month::ALL.iter().map(|m| m.length(true)).sum::<u32>(), 366);
+        assert_eq!(Date::new(Month::Jan, 1).day_of_year(false), 1);
+        assert_eq!(Date::new(Month::Mar, 1).day_of_year(false), 60);
+        assert_eq!(Date::new(Month::Mar, 1).day_of_year(true), 61);
+        assert_eq!(Date::new(Month::Dec, 31).day_of_year(true), 366);
+
+        assert_eq!(Date::from_day_of_year(60, true), Some(Date::new(Month::Feb, 29)));
+        assert_eq!(Date::from_day_of_year(365, false), Some(Date::new(Month::Dec, 31)));
+        assert_eq!(Date::from_day_of_year(366, false), None);
+        assert_eq!(Date::from_day_of_year(0, false), None);
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(weekday_after(Weekday::Sunday, 1), Weekday::Monday);
+        assert_eq!(weekday_after(Weekday::Monday, -1), Weekday::Sunday);
+        assert_eq!(weekday_after(Weekday::Tuesday, -71), Weekday::Monday);
+        // 2024: Tuesday the 12th of March, and Monday the 1st of January
+        let known = Date::new(Month::Mar, 12);
+        assert_eq!(weekday_on(Date::new(Month::Jan, 1), known, Weekday::Tuesday, true), Weekday::Monday);
+        assert_eq!(weekday_on(Date::new(Month::Dec, 31), known, Weekday::Tuesday, true), Weekday::Tuesday);
+        assert_eq!(weekday_on(known, known, Weekday::Tuesday, true), Weekday::Tuesday);
+    }
+
+    #[test]
+    fn test_leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(2023));
+        assert!(!is_leap_year(1900));
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(0));
+        assert!(is_leap_year(-4));
+        assert!(!is_leap_year(-100));
+    }
+
+    #[test]
+    fn test_year_dates() {
+        assert_eq!(YearDate::new(1, Month::Jan, 1).days(), 0);
+        assert_eq!(YearDate::new(1970, Month::Jan, 1).weekday(), Weekday::Thursday);
+        assert_eq!(YearDate::new(2000, Month::Feb, 29).weekday(), Weekday::Tuesday);
+        assert_eq!(YearDate::new(1900, Month::Mar, 1).weekday(), Weekday::Thursday);
+        assert_eq!(YearDate::new(2024, Month::Mar, 12).weekday(), Weekday::Tuesday);
+
+        let new_year = YearDate::new(1999, Month::Dec, 31);
+        assert_eq!(new_year.add_days(1), YearDate::new(2000, Month::Jan, 1));
+        assert_eq!(new_year.add_days(61), YearDate::new(2000, Month::Mar, 1));
+        assert_eq!(new_year.add_days(-365), YearDate::new(1998, Month::Dec, 31));
+        assert_eq!(new_year.days_until(YearDate::new(2099, Month::Dec, 31)), 36_525);
+        assert_eq!(YearDate::new(2100, Month::Mar, 1).days_until(YearDate::new(2100, Month::Feb, 28)), -1);
+        assert_eq!(YearDate::new(0, Month::Dec, 31).add_days(1), YearDate::new(1, Month::Jan, 1));
+        assert_eq!(YearDate::new(2024, Month::Mar, 12).to_string(), "Mar 12 2024");
+    }
+
+    #[test]
+    fn test_days_round_trip() {
+        let mut date = YearDate::new(-401, Month::Jan, 1);
+        for days in date.days()..YearDate::new(2401, Month::Jan, 1).days() {
+            assert_eq!(date.days(), days);
+            assert_eq!(YearDate::from_days(days), date);
+            let next = match next_day(date.date(), is_leap_year(date.year)) {
+                Some(next) => YearDate::new(date.year, next.month, next.day),
+                None => YearDate::new(date.year + 1, Month::Jan, 1),
+            };
+            assert_eq!(weekday_after(date.weekday(), 1), next.weekday());
+            date = next;
+        }
+    }
+
+    #[test]
+    fn test_weekday_on_every_date() {
+        for &leap in &[false, true] {
+            let mut sources = vec![Date::new(Month::Jan, 1)];
+            while let Some(next) = next_day(*sources.last().unwrap(), leap) {
+                sources.push(next);
+            }
+            assert_eq!(sources.len(), if leap { 366 } else { 365 });
+
+            for &source in &sources {
+                for &source_weekday in &Weekday::ALL {
+                    let targets = weekdays_by_stepping(source, source_weekday, leap);
+                    assert_eq!(targets.len(), sources.len());
+                    for &(target, expected) in &targets {
+                        assert_eq!(weekday_on(target, source, source_weekday, leap), expected,
+                                   "{} from {} {}, leap: {}", target, source_weekday, source, leap);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! The eight compass directions of grid puzzles, plus staying put, in the `N`/`NE`/.../`WAIT`
+//! form CodinGame reads and writes them.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A cell, as `(x, y)` with y growing downwards.
+pub type Point = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+    Wait,
+}
+
+use self::Direction::*;
+
+impl Direction {
+    /// The directions that move, clockwise from north.
+    pub const ALL: [Direction; 8] = [N, NE, E, SE, S, SW, W, NW];
+
+    /// The directions along the axes, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [N, E, S, W];
+
+    /// The one-cell move this direction makes.
+    pub fn offset(self) -> Point {
+        match self {
+            N => (0, -1),
+            NE => (1, -1),
+            E => (1, 0),
+            SE => (1, 1),
+            S => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (-1, -1),
+            Wait => (0, 0),
+        }
+    }
+
+    /// The direction making a one-cell move, if `offset` is one.
+    pub fn from_offset(offset: Point) -> Option<Direction> {
+        Direction::ALL.iter().chain(&[Wait]).find(|dir| dir.offset() == offset).copied()
+    }
+
+    /// The direction to take from `from` to get closer to `to` on an open grid, going diagonally
+    /// until lined up, or `Wait` if they're the same cell.
+    pub fn toward(from: Point, to: Point) -> Direction {
+        Direction::from_offset(((to.0 - from.0).signum(), (to.1 - from.1).signum())).unwrap()
+    }
+
+    /// Where a move in this direction from `from` ends up.
+    pub fn step(self, from: Point) -> Point {
+        let (dx, dy) = self.offset();
+        (from.0 + dx, from.1 + dy)
+    }
+
+    /// This direction turned clockwise by `eighths` of a turn, or anticlockwise if negative.
+    /// `Wait` stays as it is.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        match Direction::ALL.iter().position(|&dir| dir == self) {
+            Some(i) => Direction::ALL[(i as i32 + eighths).rem_euclid(8) as usize],
+            None => Wait,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match *self {
+            N => "N",
+            NE => "NE",
+            E => "E",
+            SE => "SE",
+            S => "S",
+            SW => "SW",
+            W => "W",
+            NW => "NW",
+            Wait => "WAIT",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError {
+    pub text: String,
+}
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not a direction", self.text)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Direction::ALL.iter()
+                      .chain(&[Wait])
+                      .find(|dir| dir.to_string() == s.trim())
+                      .copied()
+                      .ok_or_else(|| ParseDirectionError { text: s.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        for dir in Direction::ALL.iter().chain(&[Wait]) {
+            assert_eq!(dir.to_string().parse::<Direction>(), Ok(*dir));
+        }
+        assert_eq!(" NE\n".parse::<Direction>(), Ok(NE));
+        assert_eq!("ne".parse::<Direction>().unwrap_err().to_string(), "\"ne\" is not a direction");
+        assert_eq!(format!("{:<4}|", S), "S   |");
+    }
+
+    #[test]
+    fn test_offsets() {
+        for dir in Direction::ALL.iter().chain(&[Wait]) {
+            assert_eq!(Direction::from_offset(dir.offset()), Some(*dir));
+        }
+        assert_eq!(Direction::from_offset((2, 0)), None);
+        assert_eq!(SW.step((5, 5)), (4, 6));
+        assert_eq!(Direction::toward((5, 5), (9, 2)), NE);
+        assert_eq!(Direction::toward((5, 5), (5, 9)), S);
+        assert_eq!(Direction::toward((5, 5), (5, 5)), Wait);
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(N.rotate(1), NE);
+        assert_eq!(N.rotate(-1), NW);
+        assert_eq!(W.rotate(10), N);
+        assert_eq!(SE.opposite(), NW);
+        assert_eq!(Wait.rotate(3), Wait);
+    }
+}
@@ -0,0 +1,203 @@
+//! Big letters drawn in ASCII art, as in the ASCII Art puzzle: every glyph is a block of
+//! `width` by `height` characters, and text is rendered by putting glyphs side by side.
+//!
+//! CodinGame hands a font over as a strip, `height` lines holding the glyphs for `A` to `Z`
+//! and then `?` one after another. Saved on its own, a font is a `width height` line, a line
+//! of the characters it has glyphs for, then the strip:
+//!
+//! ```text
+//! 1 2
+//! AB?
+//! |B?
+//! |B?
+//! ```
+//!
+//! The last glyph stands in for any character the font doesn't have.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The characters CodinGame's fonts draw, in strip order.
+pub const CODINGAME_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ?";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontError {
+    Io(String),
+    /// A first line that isn't a width and a height.
+    BadHeader { text: String },
+    /// No characters to draw, or the same character twice.
+    BadChars { text: String },
+    MissingRows { expected: usize, found: usize },
+    /// A strip row longer than the glyphs laid end to end.
+    WrongWidth { row: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FontError::Io(ref msg) => write!(f, "couldn't read or write the font: {}", msg),
+            FontError::BadHeader { ref text } => write!(f, "expected a width and a height, found '{}'", text),
+            FontError::BadChars { ref text } => write!(f, "bad font characters '{}'", text),
+            FontError::MissingRows { expected, found } => write!(f, "expected {} rows, found {}", expected, found),
+            FontError::WrongWidth { row, expected, found } => {
+                write!(f, "row {} is {} characters, expected at most {}", row, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for FontError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Font {
+    width: usize,
+    height: usize,
+    chars: Vec<char>,
+    // row by row, each the glyphs laid end to end in `chars` order
+    rows: Vec<Vec<char>>,
+}
+
+impl Font {
+    /// The font in CodinGame's strip of `A` to `Z` and `?`.
+    pub fn from_strip<S: AsRef<str>>(width: usize, height: usize, rows: &[S]) -> Result<Self, FontError> {
+        Font::with_chars(CODINGAME_CHARS, width, height, rows)
+    }
+
+    /// The font in a strip of glyphs for `chars`, the last standing in for everything else.
+    /// Rows can be short, as when trailing spaces get lost, and are padded with spaces.
+    pub fn with_chars<S: AsRef<str>>(chars: &str, width: usize, height: usize, rows: &[S]) -> Result<Self, FontError> {
+        let chars = chars.chars().collect::<Vec<_>>();
+        let distinct = chars.iter().enumerate().all(|(i, c)| !chars[..i].contains(c));
+        if chars.is_empty() || !distinct {
+            return Err(FontError::BadChars { text: chars.into_iter().collect() });
+        }
+        if rows.len() < height {
+            return Err(FontError::MissingRows { expected: height, found: rows.len() });
+        }
+
+        let strip_width = width * chars.len();
+        let rows = rows[..height].iter()
+            .enumerate()
+            .map(|(y, row)| {
+                let mut row = row.as_ref().chars().collect::<Vec<_>>();
+                if row.len() > strip_width {
+                    return Err(FontError::WrongWidth { row: y, expected: strip_width, found: row.len() });
+                }
+                row.resize(strip_width, ' ');
+                Ok(row)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Font { width, height, chars, rows })
+    }
+
+    /// Reads a font saved with `save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        fs::read_to_string(path).map_err(|e| FontError::Io(e.to_string()))?.parse()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), FontError> {
+        fs::write(path, self.to_string()).map_err(|e| FontError::Io(e.to_string()))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Where `c`'s glyph is in the strip: its own, its capital's, or the stand-in's.
+    fn glyph_index(&self, c: char) -> usize {
+        let find = |c| self.chars.iter().position(|&have| have == c);
+        find(c).or_else(|| find(c.to_ascii_uppercase())).unwrap_or(self.chars.len() - 1)
+    }
+
+    /// Row `y` of the glyph drawing `c`.
+    pub fn glyph_row(&self, c: char, y: usize) -> &[char] {
+        let start = self.glyph_index(c) * self.width;
+        &self.rows[y][start..start + self.width]
+    }
+
+    /// `text` in big letters, as `height` lines.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        (0..self.height).map(|y| text.chars().flat_map(|c| self.glyph_row(c, y)).collect()).collect()
+    }
+}
+
+/// The saved form that `load` reads back.
+impl fmt::Display for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {}", self.width, self.height)?;
+        writeln!(f, "{}", self.chars.iter().collect::<String>())?;
+        for row in &self.rows {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Font {
+    type Err = FontError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let header = lines.next().unwrap_or("");
+        let size = header.split_whitespace().map(str::parse::<usize>).collect::<Vec<_>>();
+        let (width, height) = match size[..] {
+            [Ok(width), Ok(height)] => (width, height),
+            _ => return Err(FontError::BadHeader { text: header.to_string() }),
+        };
+        let chars = lines.next().unwrap_or("");
+        Font::with_chars(chars, width, height, &lines.collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The first three letters of a 2x2 font, then the stand-in.
+    fn small() -> Font {
+        Font::with_chars("ABC?", 2, 2, &["/\\|)(_??", "||||(_ ."]).unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let font = small();
+        assert_eq!(font.render("CAB"), vec!["(_/\\|)", "(_||||"]);
+        assert_eq!(font.render("ab"), font.render("AB"));
+        assert_eq!(font.render("A-Z"), vec!["/\\????", "|| . ."]);
+        assert_eq!(font.render(""), vec!["", ""]);
+        assert_eq!(font.glyph_row('x', 1), &[' ', '.']);
+    }
+
+    #[test]
+    fn test_strip() {
+        let rows = ["#".repeat(26) + "?", ".".repeat(20)];
+        let font = Font::from_strip(1, 2, &rows).unwrap();
+        assert_eq!(font.render("Hi!"), vec!["##?", ".. "]);
+
+        assert_eq!(Font::from_strip(1, 3, &rows), Err(FontError::MissingRows { expected: 3, found: 2 }));
+        assert_eq!(Font::from_strip(1, 1, &["#".repeat(28)]).unwrap_err().to_string(),
+                   "row 0 is 28 characters, expected at most 27");
+        assert_eq!(Font::with_chars("AA", 1, 1, &["ab"]), Err(FontError::BadChars { text: "AA".to_string() }));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let font = small();
+        assert_eq!(font.to_string(), "2 2\nABC?\n/\\|)(_??\n||||(_ .\n");
+        assert_eq!(font.to_string().parse::<Font>(), Ok(font.clone()));
+        assert_eq!("2\nAB".parse::<Font>(), Err(FontError::BadHeader { text: "2".to_string() }));
+
+        let path = std::env::temp_dir().join(format!("codingame-font-{}.txt", std::process::id()));
+        font.save(&path).unwrap();
+        assert_eq!(Font::load(&path), Ok(font));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(Font::load(&path), Err(FontError::Io(_))));
+    }
+}
@@ -0,0 +1,279 @@
+//! Rectangular grids of cells, as most puzzles hand over their boards: `height` lines of
+//! `width` characters each.
+//!
+//! Cells are addressed by `(x, y)` points like everywhere else in the library, with y growing
+//! downwards, so points off the grid (negative ones included) are simply not found.
+
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+pub use crate::direction::Point;
+use crate::direction::Direction;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row that isn't as wide as the first one.
+    Ragged { row: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GridError::Ragged { row, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", row, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // row by row
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+        where T: Clone
+    {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// A `width` by `height` grid with `f` of each point in it.
+    pub fn from_fn<F: FnMut(Point) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height * width).map(|i| f(((i % width) as i32, (i / width) as i32))).collect();
+        Grid { width, height, cells }
+    }
+
+    /// A grid of the given rows, top first, which all have to be the same width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged { row: y, expected: width, found: row.len() });
+            }
+            cells.extend(row);
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Where the cell at `p` is in `cells`.
+    fn offset(&self, p: Point) -> Option<usize> {
+        if self.contains(p) { Some(p.1 as usize * self.width + p.0 as usize) } else { None }
+    }
+
+    /// Whether `p` is on the grid.
+    pub fn contains(&self, p: Point) -> bool {
+        p.0 >= 0 && p.1 >= 0 && (p.0 as usize) < self.width && (p.1 as usize) < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(move |i| &mut self.cells[i])
+    }
+
+    /// Row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} is off a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every cell with its point, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| (((i % width) as i32, (i / width) as i32), cell))
+    }
+
+    /// The cells one step from `p` in each of `dirs`, that are on the grid.
+    pub fn neighbours<'a>(&'a self, p: Point, dirs: &'a [Direction]) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        dirs.iter().filter_map(move |dir| {
+            let next = dir.step(p);
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    /// The cells above, right of, below and left of `p`, that are on the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.neighbours(p, &Direction::ORTHOGONAL)
+    }
+
+    /// The cells around `p`, diagonals included, clockwise from above it.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.neighbours(p, &Direction::ALL)
+    }
+
+    /// The cells met going from `p` in direction `dir` to the edge of the grid, `p` itself not
+    /// included. Going nowhere meets nothing.
+    pub fn ray(&self, p: Point, dir: Direction) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let first = if dir == Direction::Wait { None } else { Some(dir.step(p)) };
+        iter::successors(first, move |&next| Some(dir.step(next)))
+            .map_while(move |next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    /// The first cell going from `p` in direction `dir` that `stop` picks, with how many steps
+    /// away it is, or nothing if the edge comes first.
+    pub fn cast<F: FnMut(&T) -> bool>(&self, p: Point, dir: Direction, mut stop: F) -> Option<(Point, usize)> {
+        self.ray(p, dir)
+            .enumerate()
+            .find(|&(_, (_, cell))| stop(cell))
+            .map(|(steps, (next, _))| (next, steps + 1))
+    }
+
+    /// A grid the same shape, of `f` applied to each cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl Grid<char> {
+    /// The grid of characters in the given lines, as CodinGame sends a board.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, GridError> {
+        Grid::from_rows(lines.iter().map(|line| line.as_ref().chars().collect()).collect())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{:?} is off a {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p).unwrap_or_else(|| panic!("{:?} is off a {}x{} grid", p, width, height))
+    }
+}
+
+/// One line per row, with the cells written one after another.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::from_lines(&["0.1", "2.3"]).unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], '3');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert_eq!(grid.to_string(), "0.1\n2.3");
+
+        assert_eq!(Grid::from_lines(&["..", "...", ""]), Err(GridError::Ragged { row: 1, expected: 2, found: 3 }));
+        let empty = Grid::from_lines::<&str>(&[]).unwrap();
+        assert_eq!((empty.width(), empty.height(), empty.rows().count()), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = sample();
+        grid[(1, 0)] = '#';
+        *grid.get_mut((1, 1)).unwrap() = '#';
+        assert_eq!(grid.get_mut((5, 5)), None);
+
+        assert_eq!(grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>(), vec!["0#1", "2#3"]);
+        assert_eq!(grid.column(2).collect::<String>(), "13");
+        assert_eq!(grid.columns().map(|col| col.collect::<String>()).collect::<Vec<_>>(), vec!["02", "##", "13"]);
+        assert_eq!(grid.cells().filter(|&(_, &c)| c == '#').map(|(p, _)| p).collect::<Vec<_>>(), vec![(1, 0), (1, 1)]);
+        assert_eq!(grid.map(|&c| c == '#').to_string(), "falsetruefalse\nfalsetruefalse");
+        assert_eq!(Grid::from_fn(3, 2, |(x, y)| x + y).to_string(), "012\n123");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        let points = |cells: Vec<(Point, &i32)>| cells.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+        assert_eq!(points(grid.neighbours4((1, 1)).collect()), vec![(1, 0), (2, 1), (1, 2), (0, 1)]);
+        assert_eq!(points(grid.neighbours4((0, 0)).collect()), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(points(grid.neighbours8((2, 2)).collect()), vec![(2, 1), (1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::from_lines(&["0..1", ".2..", "...3"]).unwrap();
+        let ray = |p, dir| grid.ray(p, dir).map(|(_, &c)| c).collect::<String>();
+        assert_eq!(ray((0, 0), Direction::E), "..1");
+        assert_eq!(ray((0, 0), Direction::SE), "2.");
+        assert_eq!(ray((1, 1), Direction::NW), "0");
+        assert_eq!(ray((3, 2), Direction::N), ".1");
+        assert_eq!(ray((0, 0), Direction::W), "");
+        assert_eq!(ray((0, 0), Direction::Wait), "");
+        assert_eq!(grid.ray((1, 0), Direction::S).map(|(p, _)| p).collect::<Vec<_>>(), vec![(1, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_cast() {
+        let grid = Grid::from_lines(&["0..1", ".2..", "...3"]).unwrap();
+        let digit = |c: &char| c.is_ascii_digit();
+        assert_eq!(grid.cast((0, 0), Direction::E, digit), Some(((3, 0), 3)));
+        assert_eq!(grid.cast((0, 0), Direction::SE, digit), Some(((1, 1), 1)));
+        assert_eq!(grid.cast((3, 2), Direction::N, digit), Some(((3, 0), 2)));
+        assert_eq!(grid.cast((3, 2), Direction::NW, digit), None);
+        assert_eq!(grid.cast((3, 0), Direction::SW, |&c| c == '.'), Some(((2, 1), 1)));
+        assert_eq!(grid.cast((1, 1), Direction::Wait, digit), None);
+
+        // from the middle of a 5x5 grid, the edge is two steps away whichever way
+        let open = Grid::new(5, 5, false);
+        for &dir in &Direction::ALL {
+            assert_eq!(open.cast((2, 2), dir, |_| false), None);
+            assert_eq!(open.cast((2, 2), dir, |_| true), Some((dir.step((2, 2)), 1)));
+            assert_eq!(open.ray((2, 2), dir).count(), 2);
+        }
+    }
+}
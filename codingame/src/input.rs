@@ -0,0 +1,306 @@
+//! Line-oriented parsing of CodinGame's stdin protocol.
+//!
+//! `Input` wraps any `BufRead` (stdin, or a `&[u8]` in tests) and counts lines as it goes, so
+//! every failure can say which line it was on and what that line held. Each reader comes in
+//! a fallible `try_` form returning an `InputError`, and a panicking form for solutions that
+//! have no better option than to stop.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, StdinLock};
+use std::str::{FromStr, SplitWhitespace};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputErrorKind {
+    Io(String),
+    Eof,
+    MissingValue { expected: usize, found: usize },
+    ExtraValues { expected: usize },
+    Invalid { token: String, reason: String },
+    WrongWidth { expected: usize, found: usize },
+}
+
+/// What went wrong, with the 1-based number and contents of the line it happened on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub line: usize,
+    pub text: String,
+    pub kind: InputErrorKind,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if self.kind != InputErrorKind::Eof {
+            write!(f, " \"{}\"", self.text)?;
+        }
+        match self.kind {
+            InputErrorKind::Io(ref msg) => write!(f, ": read failed: {}", msg),
+            InputErrorKind::Eof => write!(f, ": unexpected end of input"),
+            InputErrorKind::MissingValue { expected, found } => {
+                write!(f, ": expected {} values, found {}", expected, found)
+            }
+            InputErrorKind::ExtraValues { expected } => {
+                write!(f, ": expected {} values, found more", expected)
+            }
+            InputErrorKind::Invalid { ref token, ref reason } => {
+                write!(f, ": couldn't parse \"{}\": {}", token, reason)
+            }
+            InputErrorKind::WrongWidth { expected, found } => {
+                write!(f, ": expected {} cells, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Anything that can be read from the whitespace-separated values of one line: single values
+/// and tuples of them.
+pub trait FromLine: Sized {
+    /// How many values this takes from the line.
+    const ARITY: usize;
+
+    /// Takes this value's words from `words`, which has already yielded `taken` of them.
+    fn from_words(words: &mut SplitWhitespace, taken: usize) -> Result<Self, InputErrorKind>;
+}
+
+fn next_word<T>(words: &mut SplitWhitespace, taken: usize, arity: usize) -> Result<T, InputErrorKind>
+    where T: FromStr, T::Err: fmt::Display
+{
+    let word = words.next().ok_or(InputErrorKind::MissingValue { expected: arity, found: taken })?;
+    word.parse::<T>().map_err(|e| InputErrorKind::Invalid { token: word.to_string(), reason: e.to_string() })
+}
+
+macro_rules! from_line_value {
+    ($($t:ty),+) => {$(
+        impl FromLine for $t {
+            const ARITY: usize = 1;
+
+            fn from_words(words: &mut SplitWhitespace, taken: usize) -> Result<Self, InputErrorKind> {
+                next_word(words, taken, 1)
+            }
+        }
+    )+};
+}
+
+from_line_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool, char, String);
+
+macro_rules! from_line_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: FromStr),+> FromLine for ($($t,)+)
+            where $(<$t as FromStr>::Err: fmt::Display),+
+        {
+            const ARITY: usize = [$(stringify!($t)),+].len();
+
+            fn from_words(words: &mut SplitWhitespace, taken: usize) -> Result<Self, InputErrorKind> {
+                let mut taken = taken;
+                Ok(($(next_word::<$t>(words, { taken += 1; taken - 1 }, Self::ARITY)?,)+))
+            }
+        }
+    };
+}
+
+from_line_tuple!(A, B);
+from_line_tuple!(A, B, C);
+from_line_tuple!(A, B, C, D);
+from_line_tuple!(A, B, C, D, E);
+from_line_tuple!(A, B, C, D, E, F);
+
+/// A line-counting reader over the puzzle's input.
+pub struct Input<R> {
+    reader: R,
+    line: usize,
+}
+
+impl<R: BufRead> Input<R> {
+    pub fn new(reader: R) -> Self {
+        Input { reader, line: 0 }
+    }
+
+    /// How many lines have been read so far.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn error(&self, text: &str, kind: InputErrorKind) -> InputError {
+        InputError { line: self.line, text: text.to_string(), kind }
+    }
+
+    /// The next line without its line ending, or `None` at the end of the input.
+    pub fn try_next_line(&mut self) -> Result<Option<String>, InputError> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+                let len = line.trim_end_matches(&['\n', '\r'][..]).len();
+                line.truncate(len);
+                Ok(Some(line))
+            }
+            Err(e) => Err(InputError { line: self.line + 1, text: String::new(), kind: InputErrorKind::Io(e.to_string()) }),
+        }
+    }
+
+    /// The next line without its line ending. Running out of input is an error.
+    pub fn try_read_line(&mut self) -> Result<String, InputError> {
+        match self.try_next_line()? {
+            Some(line) => Ok(line),
+            None => Err(InputError { line: self.line + 1, text: String::new(), kind: InputErrorKind::Eof }),
+        }
+    }
+
+    /// One line holding exactly the values of `T`, eg `i32` or `(i32, i32, String)`.
+    pub fn try_parse<T: FromLine>(&mut self) -> Result<T, InputError> {
+        let line = self.try_read_line()?;
+        self.try_parse_text(&line)
+    }
+
+    /// Parses `text` like `try_parse`, reporting errors against the last line read. For lines
+    /// that have to be looked at before it's known what they hold.
+    pub fn try_parse_text<T: FromLine>(&self, text: &str) -> Result<T, InputError> {
+        let mut words = text.split_whitespace();
+        let value = T::from_words(&mut words, 0).map_err(|kind| self.error(text, kind))?;
+        if words.next().is_some() {
+            return Err(self.error(text, InputErrorKind::ExtraValues { expected: T::ARITY }));
+        }
+        Ok(value)
+    }
+
+    /// `n` lines of `T`, one value (or tuple) per line.
+    pub fn try_lines<T: FromLine>(&mut self, n: usize) -> Result<Vec<T>, InputError> {
+        (0..n).map(|_| self.try_parse::<T>()).collect()
+    }
+
+    /// All the values on one line, however many there are.
+    pub fn try_words<T>(&mut self) -> Result<Vec<T>, InputError>
+        where T: FromStr, T::Err: fmt::Display
+    {
+        let line = self.try_read_line()?;
+        line.split_whitespace()
+            .map(|word| {
+                word.parse::<T>().map_err(|e| {
+                    self.error(&line, InputErrorKind::Invalid { token: word.to_string(), reason: e.to_string() })
+                })
+            })
+            .collect()
+    }
+
+    /// `height` rows of exactly `width` characters each.
+    pub fn try_grid(&mut self, width: usize, height: usize) -> Result<Vec<Vec<char>>, InputError> {
+        (0..height)
+            .map(|_| {
+                let line = self.try_read_line()?;
+                let row = line.chars().collect::<Vec<_>>();
+                if row.len() != width {
+                    return Err(self.error(&line, InputErrorKind::WrongWidth { expected: width, found: row.len() }));
+                }
+                Ok(row)
+            })
+            .collect()
+    }
+
+    pub fn read_line(&mut self) -> String {
+        self.try_read_line().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn parse<T: FromLine>(&mut self) -> T {
+        self.try_parse().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn parse_text<T: FromLine>(&self, text: &str) -> T {
+        self.try_parse_text(text).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn lines<T: FromLine>(&mut self, n: usize) -> Vec<T> {
+        self.try_lines(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn words<T>(&mut self) -> Vec<T>
+        where T: FromStr, T::Err: fmt::Display
+    {
+        self.try_words().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn grid(&mut self, width: usize, height: usize) -> Vec<Vec<char>> {
+        self.try_grid(width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Input<StdinLock<'static>> {
+    pub fn stdin() -> Self {
+        Input::new(io::stdin().lock())
+    }
+}
+
+thread_local! {
+    static STDIN: RefCell<Input<StdinLock<'static>>> = RefCell::new(Input::stdin());
+}
+
+/// Runs `f` on a shared stdin `Input`, so that line numbers carry on from call to call.
+pub fn with_stdin<T, F: FnOnce(&mut Input<StdinLock<'static>>) -> T>(f: F) -> T {
+    STDIN.with(|input| f(&mut input.borrow_mut()))
+}
+
+/// Reads one line of values: `parse_line!(input; i32, i32)` from an `Input`, or
+/// `parse_line!(i32, i32)` from stdin. A single type gives a value, several a tuple.
+#[macro_export]
+macro_rules! parse_line {
+    ($input:expr; $t:ty) => { $input.parse::<$t>() };
+    ($input:expr; $($t:ty),+) => { $input.parse::<($($t,)+)>() };
+    ($($t:ty),+) => { $crate::input::with_stdin(|input| $crate::parse_line!(input; $($t),+)) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut input = Input::new("8 13 2\n  6 2 \r\nMonday Jan 1\n4\n".as_bytes());
+        assert_eq!(input.parse::<(i32, i32, i32)>(), (8, 13, 2));
+        assert_eq!(parse_line!(input; u8, u8), (6, 2));
+        assert_eq!(input.parse::<(String, String, u32)>(), ("Monday".to_string(), "Jan".to_string(), 1));
+        let line = input.read_line();
+        assert_eq!(input.parse_text::<i32>(&line), 4);
+        assert_eq!(input.line(), 4);
+        assert_eq!(input.try_next_line(), Ok(None));
+    }
+
+    #[test]
+    fn test_lines_words_grid() {
+        let mut input = Input::new("1 2\n3 4\n5 6 7 8\n0.0\n.00\n".as_bytes());
+        assert_eq!(input.lines::<(i32, i32)>(2), vec![(1, 2), (3, 4)]);
+        assert_eq!(input.words::<u64>(), vec![5, 6, 7, 8]);
+        assert_eq!(input.grid(3, 2), vec![vec!['0', '.', '0'], vec!['.', '0', '0']]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut input = Input::new("1 x\n1\n1 2 3\nabcd\n".as_bytes());
+        let err = input.try_parse::<(i32, i32)>().unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.kind, InputErrorKind::Invalid { token: "x".to_string(), reason: "invalid digit found in string".to_string() });
+        assert_eq!(err.to_string(), "line 1 \"1 x\": couldn't parse \"x\": invalid digit found in string");
+
+        let err = input.try_parse::<(i32, i32)>().unwrap_err();
+        assert_eq!(err.to_string(), "line 2 \"1\": expected 2 values, found 1");
+        assert_eq!(input.try_parse_text::<i32>("1 x").unwrap_err().line, 2);
+
+        let err = input.try_parse::<(i32, i32)>().unwrap_err();
+        assert_eq!(err.kind, InputErrorKind::ExtraValues { expected: 2 });
+
+        let err = input.try_grid(3, 1).unwrap_err();
+        assert_eq!(err.kind, InputErrorKind::WrongWidth { expected: 3, found: 4 });
+
+        let err = input.try_read_line().unwrap_err();
+        assert_eq!(err.to_string(), "line 5: unexpected end of input");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1 \"-\": couldn't parse \"-\"")]
+    fn test_parse_panics_with_context() {
+        Input::new("-\n".as_bytes()).parse::<i32>();
+    }
+}
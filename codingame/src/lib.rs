@@ -0,0 +1,14 @@
+//! Helpers shared between the CodinGame solutions.
+//!
+//! CodinGame only accepts a single source file, so solutions that use these are run through
+//! the bundler before submission.
+
+pub mod calendar;
+pub mod direction;
+pub mod font;
+pub mod grid;
+pub mod input;
+pub mod life;
+pub mod log;
+pub mod pathing;
+pub mod skynet;
@@ -0,0 +1,330 @@
+//! Life-like cellular automata on a `Grid<bool>`: Conway's Game of Life, and the other rules
+//! written as `B3/S23` rulestrings, where a dead cell is born with any of the neighbour counts
+//! after the `B` and a live one survives with any of those after the `S`.
+//!
+//! The edge of the grid is either dead cells all round, as CodinGame's weekly puzzle has it,
+//! or wraps round to the other side. Since a grid only has so many states, every run ends up
+//! in a cycle, which `Life` notices so that long runs can skip whole periods at a time. It
+//! uses Brent's cycle detection, which keeps two grids whatever the length of the run.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::direction::{Direction, Point};
+use crate::grid::Grid;
+
+/// Which neighbour counts bring a dead cell to life and keep a live one alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    born: [bool; 9],
+    survives: [bool; 9],
+}
+
+impl Rule {
+    /// `B3/S23`, the Game of Life.
+    pub const CONWAY: Rule = Rule {
+        born: [false, false, false, true, false, false, false, false, false],
+        survives: [false, false, true, true, false, false, false, false, false],
+    };
+
+    /// Whether a cell is alive next generation, given whether it is now and how many of its
+    /// neighbours are.
+    pub fn next(&self, alive: bool, neighbours: usize) -> bool {
+        let counts = if alive { &self.survives } else { &self.born };
+        counts.get(neighbours).copied().unwrap_or(false)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &[bool; 9]| (0..9).filter(|&n| counts[n]).map(|n| n.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(&self.born), digits(&self.survives))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleError {
+    pub text: String,
+}
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not a B/S rulestring", self.text)
+    }
+}
+
+impl Error for ParseRuleError {}
+
+impl FromStr for Rule {
+    type Err = ParseRuleError;
+
+    /// Reads `B3/S23`, in either order and either case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseRuleError { text: s.to_string() };
+        let mut rule = Rule { born: [false; 9], survives: [false; 9] };
+        let (mut seen_born, mut seen_survives) = (false, false);
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let (counts, seen) = match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => (&mut rule.born, &mut seen_born),
+                Some('S') => (&mut rule.survives, &mut seen_survives),
+                _ => return Err(err()),
+            };
+            if *seen {
+                return Err(err());
+            }
+            *seen = true;
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n < 9 => counts[n as usize] = true,
+                    _ => return Err(err()),
+                }
+            }
+        }
+        if seen_born && seen_survives { Ok(rule) } else { Err(err()) }
+    }
+}
+
+/// What lies past the edge of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Boundary {
+    /// Dead cells, forever.
+    Dead,
+    /// The other side of the grid.
+    Torus,
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dead" => Ok(Boundary::Dead),
+            "torus" | "toroidal" | "wrap" => Ok(Boundary::Torus),
+            _ => Err(format!("{:?} is not a boundary, expected dead or torus", s)),
+        }
+    }
+}
+
+/// A repeating stretch of generations: the one at `start` comes round again every `period`.
+/// `start` is a generation of the `Life`, counted from its generation 0 rather than from when
+/// the cycle was looked for: the first one in the cycle from there on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Whether the cells stopped changing, dead ones included.
+    pub fn is_still_life(&self) -> bool {
+        self.period == 1
+    }
+
+    /// Whether the cells keep changing but come back to how they were.
+    pub fn is_oscillator(&self) -> bool {
+        self.period > 1
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Life {
+    cells: Grid<bool>,
+    rule: Rule,
+    boundary: Boundary,
+    generation: usize,
+}
+
+impl Life {
+    /// Generation 0 is `cells`.
+    pub fn new(cells: Grid<bool>, rule: Rule, boundary: Boundary) -> Self {
+        Life { cells, rule, boundary, generation: 0 }
+    }
+
+    pub fn cells(&self) -> &Grid<bool> {
+        &self.cells
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // How many of the cells around `p` are alive.
+    fn live_neighbours(&self, p: Point) -> usize {
+        let (width, height) = (self.cells.width() as i32, self.cells.height() as i32);
+        Direction::ALL.iter()
+            .map(|dir| dir.step(p))
+            .filter_map(|q| match self.boundary {
+                Boundary::Dead => self.cells.get(q),
+                Boundary::Torus => self.cells.get((q.0.rem_euclid(width), q.1.rem_euclid(height))),
+            })
+            .filter(|&&alive| alive)
+            .count()
+    }
+
+    /// Moves on one generation.
+    pub fn step(&mut self) {
+        let next = Grid::from_fn(self.cells.width(), self.cells.height(), |p| {
+            self.rule.next(self.cells[p], self.live_neighbours(p))
+        });
+        self.cells = next;
+        self.generation += 1;
+    }
+
+    /// Moves on `generations` generations, and returns the cycle it ran into on the way if it
+    /// did. Once it's in one, it skips whole periods rather than working them out.
+    pub fn run(&mut self, generations: usize) -> Option<Cycle> {
+        let target = self.generation + generations;
+        let cycle = self.find_cycle(generations);
+        if let Some(cycle) = cycle {
+            while self.generation < cycle.start {
+                self.step();
+            }
+            self.generation = target - (target - cycle.start) % cycle.period;
+        }
+        while self.generation < target {
+            self.step();
+        }
+        cycle
+    }
+
+    /// The cycle the cells settle into, if it comes round within `max_generations` of now, ie
+    /// if the first repeated generation is no later than that.
+    pub fn find_cycle(&self, max_generations: usize) -> Option<Cycle> {
+        // Brent's search finds any such cycle by the time the hare is three times as far on
+        let limit = max_generations.saturating_mul(3).saturating_add(2);
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        hare.step();
+        let (mut power, mut period) = (1, 1);
+        while tortoise.cells != hare.cells {
+            if hare.generation - self.generation >= limit {
+                return None;
+            }
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare.step();
+            period += 1;
+        }
+
+        // with the hare a period ahead, they first meet where the cycle starts
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        for _ in 0..period {
+            hare.step();
+        }
+        while tortoise.cells != hare.cells {
+            tortoise.step();
+            hare.step();
+        }
+        if hare.generation - self.generation > max_generations {
+            return None;
+        }
+        Some(Cycle { start: tortoise.generation, period })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(rows: &[&str]) -> Grid<bool> {
+        Grid::from_lines(rows).unwrap().map(|&c| c == '1')
+    }
+
+    fn rows(grid: &Grid<bool>) -> Vec<String> {
+        grid.rows().map(|row| row.iter().map(|&alive| if alive { '1' } else { '0' }).collect()).collect()
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!("B3/S23".parse::<Rule>(), Ok(Rule::CONWAY));
+        assert_eq!("s23/b3".parse::<Rule>(), Ok(Rule::CONWAY));
+        let highlife = "B36/S23".parse::<Rule>().unwrap();
+        assert_eq!(highlife.to_string(), "B36/S23");
+        assert!(highlife.next(false, 6));
+        assert!(!Rule::CONWAY.next(false, 6));
+        assert_eq!("B/S".parse::<Rule>().unwrap().to_string(), "B/S");
+
+        for bad in &["B3", "B3/S23/S4", "B9/S23", "X3/S23", "B3/B4", ""] {
+            assert!(bad.parse::<Rule>().is_err(), "{:?}", bad);
+        }
+        assert_eq!("B3/S2x".parse::<Rule>().unwrap_err().to_string(), "\"B3/S2x\" is not a B/S rulestring");
+        assert_eq!("Torus".parse::<Boundary>(), Ok(Boundary::Torus));
+        assert!("open".parse::<Boundary>().is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        // the puzzle's example
+        let mut life = Life::new(cells(&["01000", "00100", "11100", "00000"]), Rule::CONWAY, Boundary::Dead);
+        life.step();
+        assert_eq!(rows(life.cells()), vec!["00000", "10100", "01100", "01000"]);
+        assert_eq!(life.generation(), 1);
+
+        // a glider heading into the bottom-right corner ends up as a block on a dead edge, but
+        //  carries on round a torus, one cell every 4 generations
+        let glider = cells(&["00000", "00000", "00100", "00010", "01110"]);
+        let mut dead = Life::new(glider.clone(), Rule::CONWAY, Boundary::Dead);
+        let mut torus = Life::new(glider.clone(), Rule::CONWAY, Boundary::Torus);
+        dead.run(20);
+        torus.run(20);
+        assert_eq!(rows(dead.cells()), vec!["00000", "00000", "00000", "00110", "00110"]);
+        assert_eq!(torus.cells(), &glider);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let block = Life::new(cells(&["0000", "0110", "0110", "0000"]), Rule::CONWAY, Boundary::Dead);
+        let cycle = block.find_cycle(10).unwrap();
+        assert_eq!(cycle, Cycle { start: 0, period: 1 });
+        assert!(cycle.is_still_life());
+
+        let blinker = Life::new(cells(&["000", "111", "000"]), Rule::CONWAY, Boundary::Dead);
+        let cycle = blinker.find_cycle(10).unwrap();
+        assert_eq!(cycle, Cycle { start: 0, period: 2 });
+        assert!(cycle.is_oscillator());
+
+        // a lone cell dies, and then nothing changes
+        let lone = Life::new(cells(&["000", "010", "000"]), Rule::CONWAY, Boundary::Dead);
+        assert_eq!(lone.find_cycle(10), Some(Cycle { start: 1, period: 1 }));
+        assert_eq!(lone.find_cycle(0), None);
+
+        // a glider gets round a 5x5 torus in 20 generations
+        let glider = Life::new(cells(&["01000", "00100", "11100", "00000", "00000"]), Rule::CONWAY, Boundary::Torus);
+        assert_eq!(glider.find_cycle(100), Some(Cycle { start: 0, period: 20 }));
+        assert_eq!(glider.find_cycle(20), Some(Cycle { start: 0, period: 20 }));
+        assert_eq!(glider.find_cycle(19), None);
+
+        // the start stays where it was, however far on the cycle is looked for from
+        let mut later = glider.clone();
+        later.run(7);
+        assert_eq!(later.find_cycle(20), Some(Cycle { start: 7, period: 20 }));
+        let mut dying = lone.clone();
+        dying.run(3);
+        assert_eq!(dying.find_cycle(1), Some(Cycle { start: 3, period: 1 }));
+    }
+
+    #[test]
+    fn test_run_skips_periods() {
+        let mut blinker = Life::new(cells(&["000", "111", "000"]), Rule::CONWAY, Boundary::Dead);
+        assert_eq!(blinker.run(1_000_000_001), Some(Cycle { start: 0, period: 2 }));
+        assert_eq!(blinker.generation(), 1_000_000_001);
+        assert_eq!(rows(blinker.cells()), vec!["010", "010", "010"]);
+
+        let mut once = Life::new(cells(&["000", "111", "000"]), Rule::CONWAY, Boundary::Dead);
+        assert_eq!(once.run(1), None);
+        assert_eq!(once.run(0), None);
+        assert_eq!(once.cells(), blinker.cells());
+    }
+}
@@ -0,0 +1,260 @@
+//! Levelled logging to stderr, the only place CodinGame lets a solution talk without it being
+//! taken for an answer.
+//!
+//! `error!`, `warn!`, `info!`, `debug!` and `trace!` take `format!` arguments:
+//!
+//! ```
+//! use codingame::{debug, info};
+//!
+//! let nodes = vec![(0, 0), (2, 0)];
+//! info!("{} nodes", nodes.len());
+//! debug!("nodes: {:?}", nodes);
+//! ```
+//!
+//! Which messages get written is decided twice. `MAX_LEVEL` is fixed at compile time by the
+//! `max-level-*` features, and anything above it is compiled out. Below that, a `Filter` picks
+//! a level for each module. It comes from `set_filter`, or else the `CODINGAME_LOG` variable,
+//! or else lets through `info!` and below. A filter is written like `info,skynet=trace`: a
+//! default level, then levels for modules, matched on whole path segments so `skynet` covers
+//! `codingame::skynet` in the library and in a bundle alike. The longest match wins.
+//!
+//! A bundled submission is compiled without features, so it keeps every level.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match *self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(FilterError::UnknownLevel { level: s.to_string() }),
+        }
+    }
+}
+
+/// The most verbose level compiled in, `None` when logging is compiled out altogether. Since
+/// `None` orders before any level, `Some(level) <= MAX_LEVEL` says whether `level` is in.
+pub const MAX_LEVEL: Option<Level> = if cfg!(feature = "max-level-off") {
+    None
+} else if cfg!(feature = "max-level-error") {
+    Some(Level::Error)
+} else if cfg!(feature = "max-level-warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "max-level-info") {
+    Some(Level::Info)
+} else if cfg!(feature = "max-level-debug") {
+    Some(Level::Debug)
+} else {
+    Some(Level::Trace)
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    /// A level other than `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    UnknownLevel { level: String },
+    /// A directive with more than one `=`, or no module before it.
+    BadDirective { directive: String },
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FilterError::UnknownLevel { ref level } => write!(f, "unknown log level '{}'", level),
+            FilterError::BadDirective { ref directive } => write!(f, "bad log directive '{}'", directive),
+        }
+    }
+}
+
+impl Error for FilterError {}
+
+/// The most verbose level written for each module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    modules: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    /// Lets through `level` and below everywhere.
+    pub fn new(level: Option<Level>) -> Self {
+        Filter { default: level, modules: Vec::new() }
+    }
+
+    /// Lets through `level` and below in `module`, instead of the default.
+    pub fn module(mut self, module: &str, level: Option<Level>) -> Self {
+        self.modules.push((module.to_string(), level));
+        self
+    }
+
+    /// Whether a message at `level` from the module at `path` gets through.
+    pub fn enabled(&self, level: Level, path: &str) -> bool {
+        let path = format!("::{}::", path);
+        let max = self.modules.iter()
+            .filter(|(module, _)| path.contains(&format!("::{}::", module)))
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |&(_, max)| max);
+        Some(level) <= max
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::new(Some(Level::Info))
+    }
+}
+
+// `off` or a level
+fn parse_max(s: &str) -> Result<Option<Level>, FilterError> {
+    if s.eq_ignore_ascii_case("off") { Ok(None) } else { s.parse().map(Some) }
+}
+
+impl FromStr for Filter {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let parts = directive.split('=').map(str::trim).collect::<Vec<_>>();
+            match parts[..] {
+                [level] => filter.default = parse_max(level)?,
+                [module, level] if !module.is_empty() => filter = filter.module(module, parse_max(level)?),
+                _ => return Err(FilterError::BadDirective { directive: directive.to_string() }),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+// None until the filter is first needed.
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+/// Replaces the filter, including one already taken from `CODINGAME_LOG`.
+pub fn set_filter(filter: Filter) {
+    *FILTER.write().unwrap() = Some(filter);
+}
+
+// The filter `CODINGAME_LOG` asks for, or the default if it's unset or makes no sense.
+fn env_filter() -> Filter {
+    match env::var("CODINGAME_LOG") {
+        Ok(spec) => spec.parse().unwrap_or_else(|e| {
+            writeln!(io::stderr(), "CODINGAME_LOG: {}", e).ok();
+            Filter::default()
+        }),
+        Err(_) => Filter::default(),
+    }
+}
+
+/// Whether a message at `level` from the module at `path` would be written.
+pub fn enabled(level: Level, path: &str) -> bool {
+    if Some(level) > MAX_LEVEL {
+        return false;
+    }
+    if let Some(ref filter) = *FILTER.read().unwrap() {
+        return filter.enabled(level, path);
+    }
+    let mut filter = FILTER.write().unwrap();
+    filter.get_or_insert_with(env_filter).enabled(level, path)
+}
+
+/// Writes a message, without checking the filter; the macros do that first so the arguments
+/// are only formatted when needed.
+pub fn write(level: Level, path: &str, args: fmt::Arguments) {
+    writeln!(io::stderr(), "{:<5} {}: {}", level, path, args).ok();
+}
+
+/// Logs at the given `Level`, see the module docs.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::log::enabled(level, module_path!()) {
+            $crate::log::write(level, module_path!(), format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!("".parse::<Filter>(), Ok(Filter::default()));
+        assert_eq!("debug, codingame::skynet=trace,input=off".parse::<Filter>(),
+                   Ok(Filter::new(Some(Level::Debug)).module("codingame::skynet", Some(Level::Trace))
+                                                     .module("input", None)));
+        assert_eq!("loud".parse::<Filter>(), Err(FilterError::UnknownLevel { level: "loud".to_string() }));
+        assert_eq!("=info".parse::<Filter>().unwrap_err().to_string(), "bad log directive '=info'");
+        assert_eq!("a=b=info".parse::<Filter>().unwrap_err().to_string(), "bad log directive 'a=b=info'");
+    }
+
+    #[test]
+    fn test_filter_modules() {
+        let filter = "warn,skynet=debug,codingame::skynet=off,spoon=trace".parse::<Filter>().unwrap();
+        assert!(filter.enabled(Level::Warn, "thor"));
+        assert!(!filter.enabled(Level::Info, "thor"));
+        assert!(filter.enabled(Level::Debug, "skynet_revolution_ep1::skynet"));
+        assert!(!filter.enabled(Level::Error, "codingame::skynet"));
+        // bundled, the library sits inside the solution's crate
+        assert!(!filter.enabled(Level::Error, "skynet_revolution_ep1::codingame::skynet"));
+        // only whole segments match
+        assert!(!filter.enabled(Level::Info, "spoonful"));
+        assert!(filter.enabled(Level::Trace, "spoon::graph"));
+    }
+}
@@ -0,0 +1,187 @@
+//! Getting around a bounded grid one cell a turn, in any of the eight compass directions.
+//!
+//! A `Map` knows the grid's bounds and which cells are blocked, and finds shortest paths across
+//! it. A `Walker` follows them a step at a time, keeping track of where it is, for bots like
+//! Power of Thor that are only told their position once.
+
+use std::collections::{HashSet, VecDeque};
+
+pub use crate::direction::Point;
+use crate::direction::Direction;
+use crate::warn;
+
+/// How many moves it takes to get from `a` to `b` on an open grid, diagonals included.
+pub fn distance(a: Point, b: Point) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    width: i32,
+    height: i32,
+    blocked: HashSet<Point>,
+}
+
+impl Map {
+    /// A `width` by `height` grid with nothing in the way.
+    pub fn new(width: i32, height: i32) -> Self {
+        Map { width, height, blocked: HashSet::new() }
+    }
+
+    pub fn block(&mut self, cell: Point) {
+        self.blocked.insert(cell);
+    }
+
+    pub fn unblock(&mut self, cell: Point) {
+        self.blocked.remove(&cell);
+    }
+
+    /// Whether `cell` is on the map.
+    pub fn contains(&self, cell: Point) -> bool {
+        (0..self.width).contains(&cell.0) && (0..self.height).contains(&cell.1)
+    }
+
+    /// Whether `cell` is on the map and free to move onto.
+    pub fn is_open(&self, cell: Point) -> bool {
+        self.contains(cell) && !self.blocked.contains(&cell)
+    }
+
+    /// The open cells one move from `cell`, clockwise from north.
+    pub fn neighbours(&self, cell: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL.iter()
+                      .map(move |dir| dir.step(cell))
+                      .filter(move |&next| self.is_open(next))
+    }
+
+    /// A shortest path from `from` to `to`, not counting `from` itself, or nothing if `to` can't
+    /// be reached from `from` or either is off the map. Where there's a choice, it keeps close to
+    /// the straight line between them, which on an open map means going diagonally until lined
+    /// up.
+    pub fn path(&self, from: Point, to: Point) -> Option<Vec<Point>> {
+        if !self.contains(from) || !self.is_open(to) {
+            return None;
+        }
+
+        // moves to `to` from each cell, searching back from it until `from` turns up
+        let index = |cell: Point| (cell.1 * self.width + cell.0) as usize;
+        let mut moves = vec![None; (self.width * self.height) as usize];
+        moves[index(to)] = Some(0);
+        let mut queue = VecDeque::from(vec![to]);
+        while let Some(cell) = queue.pop_front() {
+            if cell == from {
+                break;
+            }
+            let d = moves[index(cell)].unwrap();
+            for nbr in self.neighbours(cell) {
+                if moves[index(nbr)].is_none() {
+                    moves[index(nbr)] = Some(d + 1);
+                    queue.push_back(nbr);
+                }
+            }
+        }
+
+        let straight = |cell: Point| (cell.0 - to.0).pow(2) + (cell.1 - to.1).pow(2);
+        let mut path = Vec::new();
+        let mut cell = from;
+        let mut d = (*moves.get(index(cell))?)?;
+        while d > 0 {
+            cell = self.neighbours(cell)
+                       .filter(|&nbr| moves[index(nbr)] == Some(d - 1))
+                       .min_by_key(|&nbr| straight(nbr))
+                       .unwrap();
+            path.push(cell);
+            d -= 1;
+        }
+        Some(path)
+    }
+}
+
+/// Something moving around a `Map`, that has to keep track of its own position.
+#[derive(Debug, Clone)]
+pub struct Walker {
+    pub map: Map,
+    pos: Point,
+}
+
+impl Walker {
+    pub fn new(map: Map, pos: Point) -> Self {
+        Walker { map, pos }
+    }
+
+    /// Where the walker is.
+    pub fn pos(&self) -> Point {
+        self.pos
+    }
+
+    /// Takes the first step of a shortest path to `target` and returns its direction, or returns
+    /// nothing if the walker is already there or can't get there at all. Warns if it can't get
+    /// there, or can't in the `turns_left`, though in the second case it still tries.
+    pub fn step_toward(&mut self, target: Point, turns_left: u32) -> Option<Direction> {
+        let path = match self.map.path(self.pos, target) {
+            Some(path) => path,
+            None => {
+                warn!("no way from {:?} to {:?}", self.pos, target);
+                return None;
+            }
+        };
+        if path.len() > turns_left as usize {
+            warn!("{:?} is {} moves from {:?}, with {} turns left", target, path.len(), self.pos, turns_left);
+        }
+
+        let next = *path.first()?;
+        let dir = Direction::from_offset((next.0 - self.pos.0, next.1 - self.pos.1)).unwrap();
+        self.pos = next;
+        Some(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance((0, 0), (3, -7)), 7);
+        assert_eq!(distance((2, 2), (2, 2)), 0);
+    }
+
+    #[test]
+    fn test_path() {
+        let map = Map::new(10, 5);
+        assert_eq!(map.path((0, 0), (4, 2)), Some(vec![(1, 1), (2, 2), (3, 2), (4, 2)]));
+        assert_eq!(map.path((3, 3), (3, 3)), Some(vec![]));
+        assert_eq!(map.path((0, 0), (10, 0)), None);
+        assert_eq!(map.path((-1, 1), (0, 0)), None);
+
+        // a wall down column 2 with a gap at the bottom
+        let mut map = Map::new(5, 4);
+        for y in 0..3 {
+            map.block((2, y));
+        }
+        let path = map.path((0, 0), (4, 0)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert!(path.contains(&(2, 3)));
+        assert!(path.iter().all(|&cell| map.is_open(cell)));
+
+        map.block((2, 3));
+        assert_eq!(map.path((0, 0), (4, 0)), None);
+        assert_eq!(map.path((0, 0), (2, 0)), None);
+    }
+
+    #[test]
+    fn test_walker() {
+        let mut walker = Walker::new(Map::new(40, 18), (5, 4));
+        assert_eq!(walker.step_toward((7, 4), 10), Some(Direction::E));
+        assert_eq!(walker.step_toward((7, 4), 10), Some(Direction::E));
+        assert_eq!(walker.pos(), (7, 4));
+        assert_eq!(walker.step_toward((7, 4), 10), None);
+
+        // too far for the turns left, but it still goes
+        assert_eq!(walker.step_toward((0, 0), 1), Some(Direction::NW));
+        assert_eq!(walker.pos(), (6, 3));
+
+        walker.map.block((0, 0));
+        assert_eq!(walker.step_toward((0, 0), 10), None);
+        assert_eq!(walker.pos(), (6, 3));
+    }
+}
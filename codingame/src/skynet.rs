@@ -0,0 +1,378 @@
+//! Graph, level parsing and game loop shared by the Skynet Revolution solvers.
+
+use std::io;
+use std::io::{BufRead, Write};
+use std::collections::{HashSet, HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::cell::RefCell;
+use std::env;
+use std::fs::File;
+use std::process;
+
+use crate::input::{Input, InputError, InputErrorKind};
+
+pub struct NodeInfo {
+     pub nbrs: HashSet<i32>,
+     pub gwlinks: i32,
+ }
+
+pub type Graph = HashMap<i32, RefCell<NodeInfo>>;
+
+pub fn read_level<R: BufRead>(input: &mut Input<R>) -> (Graph, HashSet<i32>) {
+    // n: the total number of nodes in the level, including the gateways
+    // l: the number of links
+    // e: the number of exit gateways
+    let (n, l, e) = input.parse::<(i32, usize, usize)>();
+
+    let mut graph = Graph::with_capacity(n as usize);
+    for node in 0..n {
+        graph.insert(node, RefCell::new(NodeInfo{ nbrs: HashSet::new(), gwlinks: 0 }));
+    }
+    let graph = graph; // remove mutability
+
+    for (n1, n2) in input.lines::<(i32, i32)>(l) { // N1 and N2 defines a link between these nodes
+        graph.get(&n1).unwrap().borrow_mut().nbrs.insert(n2);
+        graph.get(&n2).unwrap().borrow_mut().nbrs.insert(n1);
+    }
+
+    let gateways = input.lines::<i32>(e).into_iter().collect::<HashSet<_>>(); // the index of a gateway node
+
+    for gwid in &gateways {
+        for gwnbr in &graph.get(gwid).unwrap().borrow().nbrs {
+            graph.get(gwnbr).unwrap().borrow_mut().gwlinks += 1;
+        }
+    }
+
+    (graph, gateways)
+}
+
+// Distance from every node that can reach `gw` without passing through another gateway.
+pub fn gateway_distances(gw: i32, graph: &Graph, gateways: &HashSet<i32>) -> HashMap<i32, i32> {
+    let mut dists = HashMap::with_capacity(graph.len());
+    let mut queue = VecDeque::new();
+    dists.insert(gw, 0);
+    queue.push_back(gw);
+
+    while let Some(currid) = queue.pop_front() {
+        let dist = dists.get(&currid).unwrap() + 1;
+        for &nbrid in graph.get(&currid).unwrap()
+                           .borrow()
+                           .nbrs.iter()
+                           .filter(|x| !gateways.contains(x)) {
+            if let Entry::Vacant(entry) = dists.entry(nbrid) {
+                entry.insert(dist);
+                queue.push_back(nbrid);
+            }
+        }
+    }
+
+    dists
+}
+
+// The lowest remaining (gateway, node) link anywhere in the graph, for when the agent can no
+//  longer reach a gateway but we still owe the referee a cut.
+pub fn any_gateway_link(graph: &Graph, gateways: &HashSet<i32>) -> Option<(i32, i32)> {
+    let mut gws = gateways.iter().cloned().collect::<Vec<_>>();
+    gws.sort();
+    gws.iter()
+       .filter_map(|&gw| graph.get(&gw).unwrap()
+                              .borrow()
+                              .nbrs.iter()
+                              .min()
+                              .map(|&nbr| (gw, nbr)))
+       .next()
+}
+
+// Where the agent goes next from `si`: one step along its shortest path to the nearest
+//  gateway, ties going to the lower gateway and then the lower node. `None` if it can't reach
+//  any gateway.
+pub fn agent_step(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> Option<i32> {
+    let mut gws = gateways.iter().cloned().collect::<Vec<_>>();
+    gws.sort();
+    gws.iter()
+       .filter_map(|&gw| {
+           let dists = gateway_distances(gw, graph, gateways);
+           dists.get(&si).cloned().map(|dist| (dist, dists))
+       })
+       .min_by_key(|&(dist, _)| dist)
+       .map(|(dist, dists)| {
+           *graph.get(&si).unwrap()
+                .borrow()
+                .nbrs.iter()
+                .filter(|&nbr| dists.get(nbr) == Some(&(dist - 1)))
+                .min()
+                .unwrap()
+       })
+}
+
+pub fn sever<W: Write>(gw: &i32, target: &i32, graph: &Graph, out: &mut W) {
+    graph.get(gw).unwrap().borrow_mut().nbrs.remove(target);
+    graph.get(target).unwrap().borrow_mut().nbrs.remove(gw);
+    graph.get(target).unwrap().borrow_mut().gwlinks -= 1;
+
+    // Example: indices of the nodes you wish to sever the link between
+    writeln!(out, "{} {}", gw, target).unwrap();
+    out.flush().unwrap();
+}
+
+// A recorded game: the level exactly as it was read, then each turn's agent position and
+//  the link we cut in reply.
+#[derive(Debug, PartialEq)]
+pub struct GameLog {
+    pub level: Vec<String>,
+    pub turns: Vec<(i32, String)>,
+}
+
+impl GameLog {
+    // Log lines are the referee's input verbatim, with our replies prefixed by "> ".
+    pub fn read<R: BufRead>(input: R) -> Result<GameLog, InputError> {
+        let mut input = Input::new(input);
+
+        let header = input.try_read_line()?;
+        let (_, l, e) = input.try_parse_text::<(usize, usize, usize)>(&header)?;
+        let mut level = vec![header];
+        for _ in 0..(l + e) {
+            level.push(input.try_read_line()?);
+        }
+
+        let mut turns = Vec::new();
+        while let Some(line) = input.try_next_line()? {
+            let si = input.try_parse_text::<i32>(&line)?;
+            let reply = input.try_read_line()?;
+            if !reply.starts_with("> ") {
+                return Err(InputError {
+                    line: input.line(),
+                    text: reply.clone(),
+                    kind: InputErrorKind::Invalid { token: reply, reason: "replies start with \"> \"".to_string() },
+                });
+            }
+            turns.push((si, reply[2..].to_string()));
+        }
+
+        Ok(GameLog { level, turns })
+    }
+
+    // What the referee sent us over the whole game.
+    pub fn input(&self) -> String {
+        let mut input = String::new();
+        for line in &self.level {
+            input.push_str(line);
+            input.push('\n');
+        }
+        for &(si, _) in &self.turns {
+            input.push_str(&si.to_string());
+            input.push('\n');
+        }
+        input
+    }
+}
+
+// Runs a whole game, asking `choose_link` for the (gateway, node) link to cut each turn and
+//  writing everything read and replied to `log` in the `GameLog` format. Returns once the
+//  input runs out.
+pub fn play<R, W, F>(input: &mut Input<R>, out: &mut W, log: &mut dyn Write, choose_link: F)
+    where R: BufRead, W: Write, F: FnMut(i32, &Graph, &HashSet<i32>) -> (i32, i32)
+{
+    play_with_frames(input, out, log, None, choose_link, |_, _, _| Vec::new());
+}
+
+// `play`, also writing a DOT frame to `frames` each turn with the path `expected_path` says
+//  the agent will take. Without `frames`, `expected_path` is never called, so the path costs
+//  nothing on CodinGame.
+pub fn play_with_frames<R, W, F, P>(input: &mut Input<R>, out: &mut W, log: &mut dyn Write,
+                                    mut frames: Option<&mut dyn Write>,
+                                    mut choose_link: F, mut expected_path: P)
+    where R: BufRead, W: Write,
+          F: FnMut(i32, &Graph, &HashSet<i32>) -> (i32, i32),
+          P: FnMut(i32, &Graph, &HashSet<i32>) -> Vec<i32>
+{
+    let header = input.read_line();
+    let (_, l, e) = input.parse_text::<(usize, usize, usize)>(&header);
+    let mut level = header + "\n";
+    for _ in 0..(l + e) {
+        level.push_str(&input.read_line());
+        level.push('\n');
+    }
+    log.write_all(level.as_bytes()).unwrap();
+
+    let (graph, gateways) = read_level(&mut Input::new(level.as_bytes()));
+    let links = level_links(&level);
+
+    // game loop
+    for turn in 1.. {
+        let si = match input.try_parse::<i32>() { // The index of the node on which the Skynet agent is positioned this turn
+            Ok(si) => si,
+            Err(InputError { kind: InputErrorKind::Eof, .. }) => break,
+            Err(e) => panic!("{}", e),
+        };
+        writeln!(log, "{}", si).unwrap();
+
+        let (gw, target) = choose_link(si, &graph, &gateways);
+        crate::debug!("turn {}: agent on {}, cutting {} {}", turn, si, gw, target);
+
+        if let Some(frames) = frames.as_mut() {
+            let path = expected_path(si, &graph, &gateways);
+            let frame = dot_frame(turn, &links, &graph, &gateways, si, (gw, target), &path);
+            frames.write_all(frame.as_bytes()).unwrap();
+            frames.flush().unwrap();
+        }
+
+        let mut reply = Vec::new();
+        sever(&gw, &target, &graph, &mut reply);
+        out.write_all(&reply).unwrap();
+        out.flush().unwrap();
+        write!(log, "> ").unwrap();
+        log.write_all(&reply).unwrap();
+        log.flush().unwrap();
+    }
+}
+
+// Plays a recorded game back through `choose_link`, listing every turn where our reply
+//  differs from the recorded one.
+pub fn replay<F>(game: &GameLog, choose_link: F) -> Vec<String>
+    where F: FnMut(i32, &Graph, &HashSet<i32>) -> (i32, i32)
+{
+    replay_with_frames(game, None, choose_link, |_, _, _| Vec::new())
+}
+
+pub fn replay_with_frames<F, P>(game: &GameLog, frames: Option<&mut dyn Write>, choose_link: F, expected_path: P)
+                                -> Vec<String>
+    where F: FnMut(i32, &Graph, &HashSet<i32>) -> (i32, i32),
+          P: FnMut(i32, &Graph, &HashSet<i32>) -> Vec<i32>
+{
+    let mut out = Vec::new();
+    play_with_frames(&mut Input::new(game.input().as_bytes()), &mut out, &mut io::sink(), frames, choose_link, expected_path);
+    let replies = String::from_utf8(out).unwrap();
+    let mut replies = replies.lines();
+
+    let mut mismatches = Vec::new();
+    for (turn, &(si, ref expected)) in game.turns.iter().enumerate() {
+        match replies.next() {
+            Some(actual) if actual.trim() == expected.trim() => {},
+            Some(actual) => mismatches.push(format!("turn {} (agent at {}): recorded \"{}\", got \"{}\"",
+                                                    turn + 1, si, expected, actual)),
+            None => mismatches.push(format!("turn {} (agent at {}): recorded \"{}\", got nothing",
+                                            turn + 1, si, expected)),
+        }
+    }
+    mismatches
+}
+
+// Every link in a level as it was first given, before any cuts.
+pub fn level_links(level: &str) -> Vec<(i32, i32)> {
+    let mut input = Input::new(level.as_bytes());
+    let (_, l, _) = input.parse::<(usize, usize, usize)>();
+    input.lines::<(i32, i32)>(l)
+}
+
+// One turn of a game as a Graphviz graph. Gateways are red, the agent yellow and the nodes of
+//  `path` blue. Links from the original level that are gone from `graph` are drawn dashed,
+//  and `cut`, the link being severed this turn, in bold red. Several frames written one after
+//  another make a valid DOT file: `dot -Tpng -O` renders one image per turn.
+pub fn dot_frame(turn: usize, links: &[(i32, i32)], graph: &Graph, gateways: &HashSet<i32>,
+                 si: i32, cut: (i32, i32), path: &[i32]) -> String {
+    let mut nodes = graph.keys().cloned().collect::<Vec<_>>();
+    nodes.sort();
+    let path_links = path.windows(2)
+                         .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+                         .collect::<HashSet<_>>();
+
+    let mut dot = format!("graph turn_{} {{\n", turn);
+    dot.push_str(&format!("    label=\"turn {}: agent at {}, severing {} {}\";\n", turn, si, cut.0, cut.1));
+    let cut = (cut.0.min(cut.1), cut.0.max(cut.1));
+    dot.push_str("    node [style=filled, fillcolor=white];\n");
+    for node in &nodes {
+        let attrs = if gateways.contains(node) {
+            "shape=doublecircle, fillcolor=tomato"
+        } else if *node == si {
+            "fillcolor=gold"
+        } else if path.contains(node) {
+            "fillcolor=lightblue"
+        } else {
+            ""
+        };
+        if attrs.is_empty() {
+            dot.push_str(&format!("    {};\n", node));
+        } else {
+            dot.push_str(&format!("    {} [{}];\n", node, attrs));
+        }
+    }
+    for &(n1, n2) in links {
+        let link = (n1.min(n2), n1.max(n2));
+        let attrs = if link == cut {
+            " [color=red, penwidth=3]"
+        } else if !graph.get(&n1).unwrap().borrow().nbrs.contains(&n2) {
+            " [style=dashed, color=grey]"
+        } else if path_links.contains(&link) {
+            " [color=blue, penwidth=2]"
+        } else {
+            ""
+        };
+        dot.push_str(&format!("    {} -- {}{};\n", n1, n2, attrs));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+// Entry point for the solvers. With no arguments this plays against stdin/stdout as on
+//  CodinGame. `--record <log>` also saves the game to <log>, `--replay <log>` plays a saved
+//  game back instead and reports any decision that has changed, and `--dot <file>` writes a
+//  frame per turn to <file> in either mode.
+pub fn run<F, P>(choose_link: F, expected_path: P)
+    where F: FnMut(i32, &Graph, &HashSet<i32>) -> (i32, i32),
+          P: FnMut(i32, &Graph, &HashSet<i32>) -> Vec<i32>
+{
+    fn usage() -> ! {
+        writeln!(io::stderr(), "usage: [--record <log> | --replay <log>] [--dot <file>]").ok();
+        process::exit(2);
+    }
+
+    let mut record = None;
+    let mut replay = None;
+    let mut dot = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--record" => record = Some(value),
+            "--replay" => replay = Some(value),
+            "--dot" => dot = Some(value),
+            _ => usage(),
+        }
+    }
+
+    let mut frames = dot.map(|path| {
+        File::create(&path).unwrap_or_else(|e| panic!("couldn't create DOT file '{}': {}", path, e))
+    });
+    let frames = frames.as_mut().map(|file| file as &mut dyn Write);
+
+    match (record, replay) {
+        (None, None) => {
+            let stdout = io::stdout();
+            play_with_frames(&mut Input::stdin(), &mut stdout.lock(), &mut io::sink(), frames,
+                             choose_link, expected_path);
+        },
+        (Some(path), None) => {
+            let stdout = io::stdout();
+            let mut log = File::create(&path).unwrap_or_else(|e| panic!("couldn't create log file '{}': {}", path, e));
+            play_with_frames(&mut Input::stdin(), &mut stdout.lock(), &mut log, frames,
+                             choose_link, expected_path);
+        },
+        (None, Some(path)) => {
+            let file = File::open(&path).unwrap_or_else(|e| panic!("couldn't open log file '{}': {}", path, e));
+            let game = GameLog::read(io::BufReader::new(file)).unwrap_or_else(|e| {
+                writeln!(io::stderr(), "{}: {}", path, e).ok();
+                process::exit(2);
+            });
+            let mismatches = replay_with_frames(&game, frames, choose_link, expected_path);
+            for mismatch in &mismatches {
+                writeln!(io::stderr(), "{}", mismatch).ok();
+            }
+            if !mismatches.is_empty() {
+                process::exit(1);
+            }
+            writeln!(io::stderr(), "{} turns replayed, all decisions match", game.turns.len()).ok();
+        },
+        (Some(_), Some(_)) => usage(),
+    }
+}
@@ -0,0 +1,126 @@
+//! A bot running as a child process, talked to over its stdin and stdout.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotError {
+    /// No line came within the time allowed.
+    TimedOut,
+    /// The bot closed its stdout, usually by exiting.
+    Closed,
+}
+
+pub struct Bot {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<String>,
+    stderr: Option<JoinHandle<String>>,
+}
+
+impl Bot {
+    /// Starts `command` with all three of its standard streams piped. Its stdout is read on a
+    /// thread of its own, so that waiting for a line can give up after a timeout.
+    pub fn spawn(command: &mut Command) -> io::Result<Bot> {
+        let mut child = command.stdin(Stdio::piped())
+                               .stdout(Stdio::piped())
+                               .stderr(Stdio::piped())
+                               .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().unwrap();
+        let mut stderr = child.stderr.take().unwrap();
+
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let stderr = thread::spawn(move || {
+            let mut text = String::new();
+            stderr.read_to_string(&mut text).ok();
+            text
+        });
+
+        Ok(Bot { child, stdin, lines, stderr: Some(stderr) })
+    }
+
+    /// Writes `text` to the bot, adding the final newline if it's missing.
+    pub fn send(&mut self, text: &str) -> io::Result<()> {
+        let stdin = self.stdin.as_mut().ok_or_else(|| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        stdin.write_all(text.as_bytes())?;
+        if !text.ends_with('\n') {
+            stdin.write_all(b"\n")?;
+        }
+        stdin.flush()
+    }
+
+    /// Closes the bot's stdin, so that it sees the end of its input.
+    pub fn close_input(&mut self) {
+        self.stdin = None;
+    }
+
+    /// The next line the bot writes, if it comes within `timeout`.
+    pub fn read_line(&mut self, timeout: Duration) -> Result<String, BotError> {
+        self.lines.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => BotError::TimedOut,
+            RecvTimeoutError::Disconnected => BotError::Closed,
+        })
+    }
+
+    /// Stops the bot, returning everything it wrote to stderr.
+    pub fn finish(mut self) -> String {
+        self.stop();
+        self.stderr.take().and_then(|handle| handle.join().ok()).unwrap_or_default()
+    }
+
+    // Killing before closing stdin spares bots the end-of-input error they'd otherwise print.
+    fn stop(&mut self) {
+        self.child.kill().ok();
+        self.stdin = None;
+        self.child.wait().ok();
+    }
+}
+
+impl Drop for Bot {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Bot {
+        Bot::spawn(Command::new("sh").arg("-c").arg(script)).unwrap()
+    }
+
+    #[test]
+    fn test_echo() {
+        let mut bot = sh("read a; echo \"got $a\"; echo note >&2; read b; echo \"got $b\"");
+        bot.send("1").unwrap();
+        assert_eq!(bot.read_line(Duration::from_secs(5)), Ok("got 1".to_string()));
+        bot.send("2\n").unwrap();
+        assert_eq!(bot.read_line(Duration::from_secs(5)), Ok("got 2".to_string()));
+        assert_eq!(bot.read_line(Duration::from_secs(5)), Err(BotError::Closed));
+        assert_eq!(bot.finish(), "note\n");
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut bot = sh("read a; exec sleep 5");
+        bot.send("1").unwrap();
+        assert_eq!(bot.read_line(Duration::from_millis(50)), Err(BotError::TimedOut));
+        bot.finish();
+    }
+}
@@ -0,0 +1,213 @@
+//! One-shot puzzles: the solution reads its whole input once, writes its whole answer and exits,
+//! and the answer is checked against a known good one.
+//!
+//! A puzzle's cases live in a directory of numbered pairs, `01.in` with the solution's input
+//! and `01.out` with what it should print. Like CodinGame, the check ignores whitespace at the
+//! end of lines and blank lines at the end of the output.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::bot::{Bot, BotError};
+
+/// How answers are compared with the expected output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Line for line.
+    Exact,
+    /// As the same lines in any order, for puzzles like Hashi that take the answer a line at a
+    /// time in whatever order the solution found it.
+    Any,
+}
+
+#[derive(Debug, Clone)]
+pub struct Case {
+    /// The pair's number, the file stem both files share.
+    pub name: String,
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+/// Every `NN.in` in `dir` that has an `NN.out` beside it, in order of name.
+pub fn cases(dir: &Path) -> io::Result<Vec<Case>> {
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let input = entry?.path();
+        if input.extension().is_none_or(|ext| ext != "in") {
+            continue;
+        }
+        let output = input.with_extension("out");
+        if output.is_file() {
+            let name = input.file_stem().unwrap().to_string_lossy().into_owned();
+            cases.push(Case { name, input, output });
+        }
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Passed,
+    /// The answer differs from the expected output, as described.
+    Failed(String),
+    /// The solution was still going when its time ran out.
+    TimedOut,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Verdict::Passed => write!(f, "passed"),
+            Verdict::Failed(ref diff) => write!(f, "failed: {}", diff),
+            Verdict::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub verdict: Verdict,
+    /// Everything the solution wrote to stdout.
+    pub output: Vec<String>,
+    /// Everything the solution wrote to stderr.
+    pub stderr: String,
+}
+
+/// Runs `command` on `case`, giving it `timeout` to write all of its answer.
+pub fn run(case: &Case, command: &mut Command, order: Order, timeout: Duration) -> io::Result<Run> {
+    let input = fs::read_to_string(&case.input)?;
+    let expected = fs::read_to_string(&case.output)?;
+
+    let mut bot = Bot::spawn(command)?;
+    // A solution that dies before reading everything is caught by the comparison below.
+    bot.send(&input).ok();
+    bot.close_input();
+
+    let deadline = Instant::now() + timeout;
+    let mut output = Vec::new();
+    let verdict = loop {
+        match bot.read_line(deadline.saturating_duration_since(Instant::now())) {
+            Ok(line) => output.push(line),
+            Err(BotError::TimedOut) => break Verdict::TimedOut,
+            Err(BotError::Closed) => {
+                break match diff(&expected, &output, order) {
+                    None => Verdict::Passed,
+                    Some(diff) => Verdict::Failed(diff),
+                }
+            }
+        }
+    };
+
+    Ok(Run { verdict, output, stderr: bot.finish() })
+}
+
+// The lines as CodinGame compares them, without trailing whitespace or trailing blank lines.
+fn normalize<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Vec<&'a str> {
+    let mut lines = lines.into_iter().map(str::trim_end).collect::<Vec<_>>();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+/// Describes how `actual` differs from `expected`, or returns `None` if it doesn't.
+pub fn diff(expected: &str, actual: &[String], order: Order) -> Option<String> {
+    let expected = normalize(expected.lines());
+    let actual = normalize(actual.iter().map(String::as_str));
+
+    match order {
+        Order::Exact => {
+            let line = (0..expected.len().max(actual.len())).find(|&i| expected.get(i) != actual.get(i))?;
+            Some(match (expected.get(line), actual.get(line)) {
+                (Some(e), Some(a)) => format!("line {}: expected {:?}, got {:?}", line + 1, e, a),
+                (Some(e), None) => format!("line {}: expected {:?}, got nothing", line + 1, e),
+                (None, Some(a)) => format!("line {}: unexpected {:?}", line + 1, a),
+                (None, None) => unreachable!(),
+            })
+        }
+        Order::Any => {
+            // How many more times each line was expected than written
+            let mut counts = HashMap::new();
+            for line in &expected {
+                *counts.entry(*line).or_insert(0) += 1;
+            }
+            for line in &actual {
+                *counts.entry(*line).or_insert(0) -= 1;
+            }
+
+            let (mut missing, mut extra) = (Vec::new(), Vec::new());
+            for line in expected.iter().chain(&actual) {
+                let lines = if counts[line] > 0 { &mut missing } else { &mut extra };
+                if counts[line] != 0 && !lines.contains(line) {
+                    lines.push(*line);
+                }
+            }
+            let mut problems = Vec::new();
+            if !missing.is_empty() {
+                problems.push(format!("missing {:?}", missing));
+            }
+            if !extra.is_empty() {
+                problems.push(format!("unexpected {:?}", extra));
+            }
+            if problems.is_empty() { None } else { Some(problems.join(", ")) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_diff_exact() {
+        assert_eq!(diff("a \nb\n\n", &lines("a\nb   "), Order::Exact), None);
+        assert_eq!(diff("a\nb\n", &lines("a\nc"), Order::Exact), Some("line 2: expected \"b\", got \"c\"".to_string()));
+        assert_eq!(diff("a\nb\n", &lines("a"), Order::Exact), Some("line 2: expected \"b\", got nothing".to_string()));
+        assert_eq!(diff("a\n", &lines("a\nb"), Order::Exact), Some("line 2: unexpected \"b\"".to_string()));
+        assert!(diff("a\nb\n", &lines("b\na"), Order::Exact).is_some());
+    }
+
+    #[test]
+    fn test_diff_any() {
+        assert_eq!(diff("a\nb\nb\n", &lines("b\na\nb"), Order::Any), None);
+        assert_eq!(diff("a\nb\nb\n", &lines("b\na\nc"), Order::Any),
+                   Some("missing [\"b\"], unexpected [\"c\"]".to_string()));
+        assert_eq!(diff("a\n", &lines("a\na"), Order::Any), Some("unexpected [\"a\"]".to_string()));
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = std::env::temp_dir().join(format!("harness-expected-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.in"), "2\n3\n").unwrap();
+        fs::write(dir.join("01.out"), "3\n2\n").unwrap();
+        fs::write(dir.join("02.in"), "no answer for this one\n").unwrap();
+        let cases = cases(&dir).unwrap();
+        assert_eq!(cases.iter().map(|case| case.name.as_str()).collect::<Vec<_>>(), vec!["01"]);
+
+        let timeout = Duration::from_secs(5);
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("cat; echo done >&2");
+        let run = super::run(&cases[0], &mut command, Order::Exact, timeout).unwrap();
+        assert_eq!(run.verdict, Verdict::Failed("line 1: expected \"3\", got \"2\"".to_string()));
+        assert_eq!(run.stderr, "done\n");
+        let run = super::run(&cases[0], &mut command, Order::Any, timeout).unwrap();
+        assert_eq!(run.verdict, Verdict::Passed);
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("exec sleep 5");
+        let run = super::run(&cases[0], &mut command, Order::Exact, Duration::from_millis(50)).unwrap();
+        assert_eq!(run.verdict, Verdict::TimedOut);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
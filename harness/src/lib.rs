@@ -0,0 +1,209 @@
+//! Plays turn-based CodinGame bots locally.
+//!
+//! Turn-based puzzles all speak the same protocol: the bot reads some initial input, then each
+//! turn reads that turn's input and answers with a line. `play` runs a bot as a child process
+//! and feeds it over pipes, while a `Referee` supplies the input, rules on each answer and says
+//! when the game is over. Like CodinGame, it gives up on a bot that takes too long to answer.
+//!
+//! One-shot puzzles, which read one input and print one answer, are checked against expected
+//! output instead, see `expected`.
+
+pub mod bot;
+pub mod expected;
+pub mod scripted;
+pub mod skynet;
+pub mod thor;
+
+use std::fmt;
+use std::io;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use bot::{Bot, BotError};
+
+/// The rules of one game.
+pub trait Referee {
+    /// What the bot reads once, before its first turn.
+    fn init_input(&self) -> String;
+
+    /// What the bot reads at the start of this turn.
+    fn turn_input(&self) -> String;
+
+    /// Plays the bot's answer for this turn, returning the outcome if it ends the game.
+    fn play(&mut self, action: &str) -> Option<Outcome>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    /// The referee ruled against the bot, for the reason given.
+    Lost(String),
+    /// The bot didn't answer in time.
+    TimedOut,
+    /// The bot exited or closed its output mid-game.
+    Crashed,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Outcome::Won => write!(f, "won"),
+            Outcome::Lost(ref reason) => write!(f, "lost: {}", reason),
+            Outcome::TimedOut => write!(f, "timed out"),
+            Outcome::Crashed => write!(f, "crashed"),
+        }
+    }
+}
+
+/// How long a bot gets to answer, and how long a game can go on.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub first_turn: Duration,
+    pub turn: Duration,
+    pub max_turns: usize,
+}
+
+impl Default for Limits {
+    /// CodinGame's usual limits: a second for the first turn, which also covers start-up, and
+    /// 150ms for each one after.
+    fn default() -> Self {
+        Limits { first_turn: Duration::from_millis(1000), turn: Duration::from_millis(150), max_turns: 1000 }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub outcome: Outcome,
+    /// The turn the game ended on, counting from 1.
+    pub turns: usize,
+    /// The bot's answer each turn.
+    pub actions: Vec<String>,
+    /// The longest the bot took to answer, not counting the first turn.
+    pub slowest: Duration,
+    /// Everything the bot wrote to stderr.
+    pub stderr: String,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} on turn {} (slowest answer {}ms)", self.outcome, self.turns, self.slowest.as_millis())
+    }
+}
+
+/// Plays a whole game between `command` and `referee`.
+pub fn play<R: Referee + ?Sized>(referee: &mut R, command: &mut Command, limits: &Limits) -> io::Result<Report> {
+    let mut bot = Bot::spawn(command)?;
+    let mut actions = Vec::new();
+    let mut slowest = Duration::from_secs(0);
+
+    let mut sent = bot.send(&referee.init_input());
+    let mut outcome = Outcome::Lost(format!("still going after {} turns", limits.max_turns));
+    let mut turn = 0;
+    while turn < limits.max_turns {
+        turn += 1;
+        sent = sent.and_then(|_| bot.send(&referee.turn_input()));
+        if sent.is_err() {
+            outcome = Outcome::Crashed;
+            break;
+        }
+
+        let timeout = if turn == 1 { limits.first_turn } else { limits.turn };
+        let start = Instant::now();
+        let action = match bot.read_line(timeout) {
+            Ok(action) => action,
+            Err(BotError::TimedOut) => {
+                outcome = Outcome::TimedOut;
+                break;
+            }
+            Err(BotError::Closed) => {
+                outcome = Outcome::Crashed;
+                break;
+            }
+        };
+        if turn > 1 {
+            slowest = slowest.max(start.elapsed());
+        }
+
+        let ended = referee.play(&action);
+        actions.push(action);
+        if let Some(ended) = ended {
+            outcome = ended;
+            break;
+        }
+    }
+
+    Ok(Report { outcome, turns: turn, actions, slowest, stderr: bot.finish() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up from 1 each turn and wants the count echoed back, until `turns` is reached.
+    struct Counter {
+        count: usize,
+        turns: usize,
+    }
+
+    impl Referee for Counter {
+        fn init_input(&self) -> String {
+            format!("{}\n", self.turns)
+        }
+
+        fn turn_input(&self) -> String {
+            format!("{}\n", self.count + 1)
+        }
+
+        fn play(&mut self, action: &str) -> Option<Outcome> {
+            self.count += 1;
+            if action != self.count.to_string() {
+                Some(Outcome::Lost(format!("expected {}, got {:?}", self.count, action)))
+            } else if self.count == self.turns {
+                Some(Outcome::Won)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    fn limits() -> Limits {
+        Limits { first_turn: Duration::from_secs(5), turn: Duration::from_secs(5), max_turns: 10 }
+    }
+
+    #[test]
+    fn test_play() {
+        let echo = "read n; while read x; do echo $x; done";
+        let report = play(&mut Counter { count: 0, turns: 3 }, &mut sh(echo), &limits()).unwrap();
+        assert_eq!(report.outcome, Outcome::Won);
+        assert_eq!(report.turns, 3);
+        assert_eq!(report.actions, vec!["1", "2", "3"]);
+
+        let report = play(&mut Counter { count: 0, turns: 3 }, &mut sh("read n; read x; echo 7"), &limits()).unwrap();
+        assert_eq!(report.outcome, Outcome::Lost("expected 1, got \"7\"".to_string()));
+        assert_eq!(report.to_string().split(" (").next(), Some("lost: expected 1, got \"7\" on turn 1"));
+
+        let report = play(&mut Counter { count: 0, turns: 20 }, &mut sh(echo), &limits()).unwrap();
+        assert_eq!(report.outcome, Outcome::Lost("still going after 10 turns".to_string()));
+    }
+
+    #[test]
+    fn test_misbehaving_bots() {
+        let script = "read n; read x; echo 1; read x; echo oops >&2; exit 1";
+        let report = play(&mut Counter { count: 0, turns: 3 }, &mut sh(script), &limits()).unwrap();
+        assert_eq!(report.outcome, Outcome::Crashed);
+        assert_eq!(report.turns, 2);
+        assert_eq!(report.stderr, "oops\n");
+
+        let limits = Limits { turn: Duration::from_millis(50), ..limits() };
+        let script = "read n; read x; echo 1; read x; exec sleep 5";
+        let report = play(&mut Counter { count: 0, turns: 3 }, &mut sh(script), &limits).unwrap();
+        assert_eq!(report.outcome, Outcome::TimedOut);
+        assert_eq!(report.turns, 2);
+    }
+}
@@ -0,0 +1,122 @@
+//! Plays a bot through one game and reports how it went, or checks a one-shot solution against
+//! a directory of expected outputs.
+//!
+//!     harness <skynet|thor|giants|script> <level> [options] -- <command> [args...]
+//!     harness expect <dir> [options] -- <command> [args...]
+//!
+//! The level file's format depends on the game, see `SkynetReferee::parse`,
+//! `ThorReferee::parse`, `GiantsReferee::parse` and `ScriptedReferee::parse`. `expect` runs the command once for each
+//! `NN.in` in the directory and compares what it prints with `NN.out`. Options:
+//!
+//!     --first-turn-ms <ms>, --turn-ms <ms>   time allowed to answer (1000 and 150)
+//!     --max-turns <n>                        turns before the game is called off (1000)
+//!     --any-order                            `expect` accepts the answer's lines in any order
+//!     --verbose                              also print every answer and the bot's stderr
+//!
+//! `expect` gives each run the first turn's time. Exits with 0 if the bot won or every case
+//! passed, and 1 otherwise.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::Duration;
+
+use harness::expected::{self, Order, Verdict};
+use harness::scripted::ScriptedReferee;
+use harness::skynet::SkynetReferee;
+use harness::thor::{GiantsReferee, ThorReferee};
+use harness::{play, Limits, Outcome, Referee};
+
+fn usage() -> ! {
+    writeln!(io::stderr(), "usage: harness <skynet|thor|giants|script|expect> <level> [--first-turn-ms <ms>] [--turn-ms <ms>] \
+                            [--max-turns <n>] [--any-order] [--verbose] -- <command> [args...]").ok();
+    process::exit(2);
+}
+
+fn fail(msg: &str) -> ! {
+    writeln!(io::stderr(), "harness: {}", msg).ok();
+    process::exit(2);
+}
+
+fn number(args: &mut impl Iterator<Item = String>) -> u64 {
+    args.next().and_then(|n| n.parse().ok()).unwrap_or_else(|| usage())
+}
+
+// Checks `command` against every case in `dir`, exiting with the overall result.
+fn expect(dir: &Path, command: &mut Command, order: Order, timeout: Duration, verbose: bool) -> ! {
+    let cases = expected::cases(dir).unwrap_or_else(|e| fail(&format!("couldn't read {}: {}", dir.display(), e)));
+    if cases.is_empty() {
+        fail(&format!("no cases in {}", dir.display()));
+    }
+
+    let mut failed = 0;
+    for case in &cases {
+        let run = expected::run(case, command, order, timeout)
+            .unwrap_or_else(|e| fail(&format!("couldn't run case {}: {}", case.name, e)));
+        if verbose {
+            for line in &run.output {
+                println!("{}", line);
+            }
+            print!("{}", run.stderr);
+        }
+        println!("{}: {}", case.name, run.verdict);
+        if run.verdict != Verdict::Passed {
+            failed += 1;
+        }
+    }
+    println!("{} of {} cases passed", cases.len() - failed, cases.len());
+
+    process::exit(if failed == 0 { 0 } else { 1 });
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let game = args.next().unwrap_or_else(|| usage());
+    let level = args.next().unwrap_or_else(|| usage());
+
+    let mut limits = Limits::default();
+    let mut order = Order::Exact;
+    let mut verbose = false;
+    loop {
+        match args.next().as_deref() {
+            Some("--first-turn-ms") => limits.first_turn = Duration::from_millis(number(&mut args)),
+            Some("--turn-ms") => limits.turn = Duration::from_millis(number(&mut args)),
+            Some("--max-turns") => limits.max_turns = number(&mut args) as usize,
+            Some("--any-order") => order = Order::Any,
+            Some("--verbose") => verbose = true,
+            Some("--") => break,
+            _ => usage(),
+        }
+    }
+    let program = args.next().unwrap_or_else(|| usage());
+    let mut command = Command::new(program);
+    command.args(args);
+
+    if game == "expect" {
+        expect(Path::new(&level), &mut command, order, limits.first_turn, verbose);
+    }
+
+    let text = fs::read_to_string(&level).unwrap_or_else(|e| fail(&format!("couldn't read {}: {}", level, e)));
+    let referee: Result<Box<dyn Referee>, String> = match game.as_str() {
+        "skynet" => SkynetReferee::parse(&text).map(|r| Box::new(r) as Box<dyn Referee>),
+        "thor" => ThorReferee::parse(&text).map(|r| Box::new(r) as Box<dyn Referee>),
+        "giants" => GiantsReferee::parse(&text).map(|r| Box::new(r) as Box<dyn Referee>),
+        "script" => ScriptedReferee::parse(&text).map(|r| Box::new(r) as Box<dyn Referee>),
+        _ => usage(),
+    };
+    let mut referee = referee.unwrap_or_else(|e| fail(&format!("{}: {}", level, e)));
+
+    let report = play(referee.as_mut(), &mut command, &limits)
+        .unwrap_or_else(|e| fail(&format!("couldn't start the bot: {}", e)));
+    if verbose {
+        for (turn, action) in report.actions.iter().enumerate() {
+            println!("turn {}: {}", turn + 1, action);
+        }
+        print!("{}", report.stderr);
+    }
+    println!("{}", report);
+
+    process::exit(if report.outcome == Outcome::Won { 0 } else { 1 });
+}
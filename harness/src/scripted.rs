@@ -0,0 +1,84 @@
+//! Replays recorded input for puzzles whose rules we don't have, like the reverse engineering
+//! puzzle, where working the rules out is the puzzle. There's nothing to rule on, so this only
+//! checks that the bot answers every turn in time with one of the allowed actions.
+
+use crate::{Outcome, Referee};
+
+pub struct ScriptedReferee {
+    init: String,
+    turns: Vec<String>,
+    allowed: Vec<String>,
+    turn: usize,
+}
+
+impl ScriptedReferee {
+    /// Plays `turns` in order after `init`. Any answer goes if `allowed` is empty.
+    pub fn new(init: &str, turns: Vec<String>, allowed: Vec<String>) -> Self {
+        ScriptedReferee { init: init.to_string(), turns, allowed, turn: 0 }
+    }
+
+    /// A script file: the initial input, then each turn's input, with a `---` line before each
+    /// turn. A first line `allow A B C` limits the answers.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut allowed = Vec::new();
+        let mut sections = vec![String::new()];
+        for (i, line) in text.lines().enumerate() {
+            if i == 0 && line.starts_with("allow ") {
+                allowed = line.split_whitespace().skip(1).map(str::to_string).collect();
+            } else if line.trim() == "---" {
+                sections.push(String::new());
+            } else {
+                let section = sections.last_mut().unwrap();
+                section.push_str(line);
+                section.push('\n');
+            }
+        }
+        if sections.len() < 2 {
+            return Err("expected at least one turn after a --- line".to_string());
+        }
+
+        let init = sections.remove(0);
+        Ok(ScriptedReferee::new(&init, sections, allowed))
+    }
+}
+
+impl Referee for ScriptedReferee {
+    fn init_input(&self) -> String {
+        self.init.clone()
+    }
+
+    fn turn_input(&self) -> String {
+        self.turns[self.turn].clone()
+    }
+
+    fn play(&mut self, action: &str) -> Option<Outcome> {
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|a| a == action.trim()) {
+            return Some(Outcome::Lost(format!("{:?} is not one of {}", action, self.allowed.join(" "))));
+        }
+        self.turn += 1;
+        if self.turn == self.turns.len() {
+            Some(Outcome::Won)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script() {
+        let mut referee = ScriptedReferee::parse("allow A B\n1\n2\n---\nx\n---\ny\nz\n").unwrap();
+        assert_eq!(referee.init_input(), "1\n2\n");
+        assert_eq!(referee.turn_input(), "x\n");
+        assert_eq!(referee.play("A"), None);
+        assert_eq!(referee.turn_input(), "y\nz\n");
+        assert_eq!(referee.play("B"), Some(Outcome::Won));
+
+        let mut referee = ScriptedReferee::parse("allow A B\n1\n---\nx\n").unwrap();
+        assert_eq!(referee.play("C"), Some(Outcome::Lost("\"C\" is not one of A B".to_string())));
+        assert!(ScriptedReferee::parse("1\n2\n").is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Skynet Revolution: each turn the bot cuts a link, then the agent takes one step along its
+//! shortest path to the nearest gateway. The bot wins once the agent can't reach a gateway
+//! at all, and loses if it ever gets into one.
+
+use std::collections::HashSet;
+
+use codingame::input::Input;
+use codingame::skynet::{agent_step, read_level, Graph};
+
+use crate::{Outcome, Referee};
+
+pub struct SkynetReferee {
+    level: String,
+    graph: Graph,
+    gateways: HashSet<i32>,
+    agent: i32,
+}
+
+impl SkynetReferee {
+    /// A game on `level`, the initial input exactly as the bot reads it, with the agent starting
+    /// on node `agent`.
+    pub fn new(level: &str, agent: i32) -> Self {
+        let (graph, gateways) = read_level(&mut Input::new(level.as_bytes()));
+        SkynetReferee { level: level.to_string(), graph, gateways, agent }
+    }
+
+    /// A game from a level file: the initial input, then a last line with the agent's start.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim_end();
+        let (level, agent) = text.rsplit_once('\n').ok_or("expected a level and the agent's start node")?;
+        let agent = agent.trim().parse::<i32>().map_err(|e| format!("bad agent start {:?}: {}", agent, e))?;
+        Ok(SkynetReferee::new(&format!("{}\n", level), agent))
+    }
+
+    /// The node the agent is on.
+    pub fn agent(&self) -> i32 {
+        self.agent
+    }
+
+    fn cut(&mut self, n1: i32, n2: i32) -> Result<(), String> {
+        let linked = self.graph.get(&n1).is_some_and(|node| node.borrow().nbrs.contains(&n2));
+        if !linked {
+            return Err(format!("there is no link between {} and {}", n1, n2));
+        }
+
+        for &(node, other) in &[(n1, n2), (n2, n1)] {
+            let mut info = self.graph.get(&node).unwrap().borrow_mut();
+            info.nbrs.remove(&other);
+            if self.gateways.contains(&other) {
+                info.gwlinks -= 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Referee for SkynetReferee {
+    fn init_input(&self) -> String {
+        self.level.clone()
+    }
+
+    fn turn_input(&self) -> String {
+        format!("{}\n", self.agent)
+    }
+
+    fn play(&mut self, action: &str) -> Option<Outcome> {
+        let nodes = action.split_whitespace().map(|word| word.parse::<i32>()).collect::<Vec<_>>();
+        let cut = match nodes[..] {
+            [Ok(n1), Ok(n2)] => self.cut(n1, n2),
+            _ => Err(format!("expected two node indices, got {:?}", action)),
+        };
+        if let Err(reason) = cut {
+            return Some(Outcome::Lost(reason));
+        }
+
+        match agent_step(self.agent, &self.graph, &self.gateways) {
+            None => Some(Outcome::Won),
+            Some(node) if self.gateways.contains(&node) => {
+                Some(Outcome::Lost(format!("the agent reached gateway {}", node)))
+            }
+            Some(node) => {
+                self.agent = node;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2 - 3, with gateways at both ends
+    const LINE_LEVEL: &str = "4 3 2
+0 1
+1 2
+2 3
+0
+3
+";
+
+    #[test]
+    fn test_parse() {
+        let referee = SkynetReferee::parse(&format!("{}2\n", LINE_LEVEL)).unwrap();
+        assert_eq!(referee.init_input(), LINE_LEVEL);
+        assert_eq!(referee.turn_input(), "2\n");
+        assert!(SkynetReferee::parse("4 3 2\n0 1\n1 2\n2 3\n0\n3\nx\n").is_err());
+    }
+
+    #[test]
+    fn test_play() {
+        let mut referee = SkynetReferee::new(LINE_LEVEL, 2);
+        assert_eq!(referee.play("3 2"), None);
+        assert_eq!(referee.agent(), 1);
+        assert_eq!(referee.play("0 1"), Some(Outcome::Won));
+
+        let mut referee = SkynetReferee::new(LINE_LEVEL, 1);
+        assert_eq!(referee.play("2 3"), Some(Outcome::Lost("the agent reached gateway 0".to_string())));
+
+        let mut referee = SkynetReferee::new(LINE_LEVEL, 1);
+        assert_eq!(referee.play("0 2"), Some(Outcome::Lost("there is no link between 0 and 2".to_string())));
+        let mut referee = SkynetReferee::new(LINE_LEVEL, 1);
+        assert_eq!(referee.play("cut 0 1"), Some(Outcome::Lost("expected two node indices, got \"cut 0 1\"".to_string())));
+    }
+}
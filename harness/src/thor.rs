@@ -0,0 +1,228 @@
+//! Power of Thor. In episode 1 Thor moves one cell a turn, in one of the eight compass
+//! directions, and has to reach the light of power before his energy runs out without leaving
+//! the map. In episode 2 giants close in on him a cell a turn, and he has to wipe them all out
+//! with a limited number of hammer strikes.
+
+use codingame::direction::{Direction, Point};
+
+use crate::{Outcome, Referee};
+
+pub const WIDTH: i32 = 40;
+pub const HEIGHT: i32 = 18;
+
+// Where Thor ends up after moving from `from` in the compass direction `action`.
+fn step(from: Point, action: &str) -> Result<Point, String> {
+    let (x, y) = match action.parse::<Direction>() {
+        Ok(Direction::Wait) => return Err("Thor can't wait here".to_string()),
+        Ok(dir) => dir.step(from),
+        Err(e) => return Err(e.to_string()),
+    };
+    if !(0..WIDTH).contains(&x) || !(0..HEIGHT).contains(&y) {
+        return Err(format!("Thor left the map at ({}, {})", x, y));
+    }
+    Ok((x, y))
+}
+
+pub struct ThorReferee {
+    light: Point,
+    start: Point,
+    thor: Point,
+    energy: i32,
+}
+
+impl ThorReferee {
+    pub fn new(light: Point, thor: Point, energy: i32) -> Self {
+        ThorReferee { light, start: thor, thor, energy }
+    }
+
+    /// A game from a level file: the initial input line, `light_x light_y thor_x thor_y`, then
+    /// a line with Thor's energy.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let values = text.split_whitespace()
+                         .map(|word| word.parse::<i32>().map_err(|e| format!("bad value {:?}: {}", word, e)))
+                         .collect::<Result<Vec<_>, _>>()?;
+        match values[..] {
+            [lx, ly, tx, ty, energy] => Ok(ThorReferee::new((lx, ly), (tx, ty), energy)),
+            _ => Err(format!("expected 5 values, found {}", values.len())),
+        }
+    }
+
+    /// Where Thor is.
+    pub fn thor(&self) -> Point {
+        self.thor
+    }
+}
+
+impl Referee for ThorReferee {
+    fn init_input(&self) -> String {
+        format!("{} {} {} {}\n", self.light.0, self.light.1, self.start.0, self.start.1)
+    }
+
+    fn turn_input(&self) -> String {
+        format!("{}\n", self.energy)
+    }
+
+    fn play(&mut self, action: &str) -> Option<Outcome> {
+        self.thor = match step(self.thor, action) {
+            Ok(thor) => thor,
+            Err(reason) => return Some(Outcome::Lost(reason)),
+        };
+        self.energy -= 1;
+
+        if self.thor == self.light {
+            Some(Outcome::Won)
+        } else if self.energy == 0 {
+            Some(Outcome::Lost("Thor ran out of energy".to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+/// How far a hammer strike reaches, in either direction along both axes.
+pub const STRIKE_RANGE: i32 = 4;
+
+/// Episode 2. Each turn Thor waits, moves or strikes, then every giant still standing takes a
+/// step toward him, diagonally if it can. Thor wins once no giants are left, and loses if one
+/// steps onto him or he runs out of strikes.
+pub struct GiantsReferee {
+    thor: Point,
+    strikes: i32,
+    giants: Vec<Point>,
+}
+
+impl GiantsReferee {
+    pub fn new(thor: Point, strikes: i32, giants: Vec<Point>) -> Self {
+        GiantsReferee { thor, strikes, giants }
+    }
+
+    /// A game from a level file: Thor's position, then the number of strikes, then the position
+    /// of each giant, all as whitespace-separated numbers.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let values = text.split_whitespace()
+                         .map(|word| word.parse::<i32>().map_err(|e| format!("bad value {:?}: {}", word, e)))
+                         .collect::<Result<Vec<_>, _>>()?;
+        if values.len() < 5 || values.len() % 2 == 0 {
+            return Err(format!("expected Thor, strikes and giants, found {} values", values.len()));
+        }
+        let giants = values[3..].chunks(2).map(|xy| (xy[0], xy[1])).collect();
+        Ok(GiantsReferee::new((values[0], values[1]), values[2], giants))
+    }
+
+    /// Where Thor is.
+    pub fn thor(&self) -> Point {
+        self.thor
+    }
+
+    /// Where the giants still standing are.
+    pub fn giants(&self) -> &[Point] {
+        &self.giants
+    }
+}
+
+impl Referee for GiantsReferee {
+    fn init_input(&self) -> String {
+        format!("{} {}\n", self.thor.0, self.thor.1)
+    }
+
+    fn turn_input(&self) -> String {
+        let mut input = format!("{} {}\n", self.strikes, self.giants.len());
+        for &(x, y) in &self.giants {
+            input.push_str(&format!("{} {}\n", x, y));
+        }
+        input
+    }
+
+    fn play(&mut self, action: &str) -> Option<Outcome> {
+        match action.trim() {
+            "WAIT" => (),
+            "STRIKE" => {
+                if self.strikes == 0 {
+                    return Some(Outcome::Lost("Thor has no strikes left".to_string()));
+                }
+                self.strikes -= 1;
+                let thor = self.thor;
+                self.giants.retain(|g| (g.0 - thor.0).abs() > STRIKE_RANGE || (g.1 - thor.1).abs() > STRIKE_RANGE);
+                if self.giants.is_empty() {
+                    return Some(Outcome::Won);
+                }
+            }
+            _ => match step(self.thor, action) {
+                Ok(thor) => self.thor = thor,
+                Err(reason) => return Some(Outcome::Lost(reason)),
+            },
+        }
+
+        for giant in self.giants.iter_mut() {
+            *giant = Direction::toward(*giant, self.thor).step(*giant);
+        }
+        if self.giants.contains(&self.thor) {
+            Some(Outcome::Lost(format!("a giant caught Thor at ({}, {})", self.thor.0, self.thor.1)))
+        } else if self.strikes == 0 {
+            Some(Outcome::Lost(format!("Thor is out of strikes with {} giants left", self.giants.len())))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let referee = ThorReferee::parse("31 4 5 4\n100\n").unwrap();
+        assert_eq!(referee.init_input(), "31 4 5 4\n");
+        assert_eq!(referee.turn_input(), "100\n");
+        assert_eq!(ThorReferee::parse("31 4 5 4\n").err(), Some("expected 5 values, found 4".to_string()));
+    }
+
+    #[test]
+    fn test_play() {
+        let mut referee = ThorReferee::new((2, 2), (0, 0), 5);
+        assert_eq!(referee.play("SE"), None);
+        assert_eq!(referee.thor(), (1, 1));
+        assert_eq!(referee.turn_input(), "4\n");
+        assert_eq!(referee.play("SE"), Some(Outcome::Won));
+
+        let mut referee = ThorReferee::new((2, 2), (0, 0), 1);
+        assert_eq!(referee.play("S"), Some(Outcome::Lost("Thor ran out of energy".to_string())));
+        let mut referee = ThorReferee::new((2, 2), (0, 0), 5);
+        assert_eq!(referee.play("NW"), Some(Outcome::Lost("Thor left the map at (-1, -1)".to_string())));
+        let mut referee = ThorReferee::new((2, 2), (0, 0), 5);
+        assert_eq!(referee.play(""), Some(Outcome::Lost("\"\" is not a direction".to_string())));
+        let mut referee = ThorReferee::new((2, 2), (0, 0), 5);
+        assert_eq!(referee.play("WAIT"), Some(Outcome::Lost("Thor can't wait here".to_string())));
+    }
+
+    #[test]
+    fn test_giants_parse() {
+        let referee = GiantsReferee::parse("3 6\n2\n0 0\n10 17\n").unwrap();
+        assert_eq!(referee.init_input(), "3 6\n");
+        assert_eq!(referee.turn_input(), "2 2\n0 0\n10 17\n");
+        assert_eq!(GiantsReferee::parse("3 6\n2\n0\n").err(),
+                   Some("expected Thor, strikes and giants, found 4 values".to_string()));
+    }
+
+    #[test]
+    fn test_giants_play() {
+        let mut referee = GiantsReferee::new((5, 5), 2, vec![(0, 0), (5, 15)]);
+        assert_eq!(referee.play("WAIT"), None);
+        assert_eq!(referee.giants(), &[(1, 1), (5, 14)]);
+        assert_eq!(referee.play("S"), None);
+        assert_eq!(referee.thor(), (5, 6));
+        assert_eq!(referee.giants(), &[(2, 2), (5, 13)]);
+        // only the first is in range
+        assert_eq!(referee.play("STRIKE"), None);
+        assert_eq!(referee.giants(), &[(5, 12)]);
+        assert_eq!(referee.play("STRIKE"), Some(Outcome::Lost("Thor is out of strikes with 1 giants left".to_string())));
+
+        let mut referee = GiantsReferee::new((5, 5), 1, vec![(3, 3), (9, 9)]);
+        assert_eq!(referee.play("STRIKE"), Some(Outcome::Won));
+        let mut referee = GiantsReferee::new((5, 5), 1, vec![(3, 3)]);
+        assert_eq!(referee.play("NW"), Some(Outcome::Lost("a giant caught Thor at (4, 4)".to_string())));
+        let mut referee = GiantsReferee::new((0, 0), 1, vec![(9, 9)]);
+        assert_eq!(referee.play("N"), Some(Outcome::Lost("Thor left the map at (0, -1)".to_string())));
+    }
+}
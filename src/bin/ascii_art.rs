@@ -0,0 +1,22 @@
+use codingame::debug;
+use codingame::font::Font;
+use codingame::input::Input;
+
+/**
+ * Auto-generated code below aims at helping you parse
+ * the standard input according to the problem statement.
+ **/
+fn main() {
+    let mut input = Input::stdin();
+
+    let l = input.parse::<usize>();
+    let h = input.parse::<usize>();
+    let t = input.read_line();
+    let rows = (0..h).map(|_| input.read_line()).collect::<Vec<_>>();
+    debug!("{}x{} font, text {:?}", l, h, t);
+
+    let font = Font::from_strip(l, h, &rows).unwrap_or_else(|e| panic!("{}", e));
+    for line in font.render(&t) {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,52 @@
+use std::env;
+use std::io::{self, Write};
+use std::process;
+
+use codingame::grid::Grid;
+use codingame::input::Input;
+use codingame::life::{Boundary, Life, Rule};
+use codingame::{debug, info};
+
+fn usage() -> ! {
+    writeln!(io::stderr(), "usage: game_of_life [--generations <n>] [--rule <B3/S23>] [--boundary <dead|torus>]").ok();
+    process::exit(2);
+}
+
+// What to run, from the command line; CodinGame runs it without arguments, for one generation
+//  of the Game of Life inside a dead border.
+fn options() -> (usize, Rule, Boundary) {
+    let (mut generations, mut rule, mut boundary) = (1, Rule::CONWAY, Boundary::Dead);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--generations" => generations = value.parse().unwrap_or_else(|_| usage()),
+            "--rule" => rule = value.parse().unwrap_or_else(|_| usage()),
+            "--boundary" => boundary = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+    (generations, rule, boundary)
+}
+
+/**
+ * Auto-generated code below aims at helping you parse
+ * the standard input according to the problem statement.
+ **/
+fn main() {
+    let (generations, rule, boundary) = options();
+    let mut input = Input::stdin();
+
+    let (width, height) = input.parse::<(usize, usize)>();
+    let cells = Grid::from_rows(input.grid(width, height)).unwrap_or_else(|e| panic!("{}", e)).map(|&c| c == '1');
+    debug!("{}x{}, {} for {} generations, {:?} boundary", width, height, rule, generations, boundary);
+
+    let mut life = Life::new(cells, rule, boundary);
+    match life.run(generations) {
+        Some(cycle) if cycle.is_still_life() => info!("still life from generation {}", cycle.start),
+        Some(cycle) => info!("oscillates with period {} from generation {}", cycle.period, cycle.start),
+        None => {}
+    }
+
+    println!("{}", life.cells().map(|&alive| if alive { '1' } else { '0' }));
+}
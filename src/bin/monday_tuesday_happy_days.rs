@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::io::BufRead;
+use std::process;
+
+use codingame::calendar::{weekday_on, Date, Month, Weekday};
+use codingame::input::Input;
+use codingame::{debug, error};
+
+// The target date's weekday, or why the input doesn't describe one.
+fn solve<R: BufRead>(input: &mut Input<R>) -> Result<Weekday, Box<dyn Error>> {
+    let leap_year = input.try_parse::<i32>()? == 1;
+    let (source_weekday, source_month, source_day_of_month) = input.try_parse::<(Weekday, Month, u32)>()?;
+    let (target_month, target_day_of_month) = input.try_parse::<(Month, u32)>()?;
+
+    let source = Date::checked(source_month, source_day_of_month, leap_year)?;
+    let target = Date::checked(target_month, target_day_of_month, leap_year)?;
+    debug!("leap: {}, {} {}, target {}", leap_year, source_weekday, source, target);
+
+    Ok(weekday_on(target, source, source_weekday, leap_year))
+}
+
+/**
+ * Auto-generated code below aims at helping you parse
+ * the standard input according to the problem statement.
+ **/
+fn main() {
+    match solve(&mut Input::stdin()) {
+        Ok(weekday) => println!("{}", weekday),
+        Err(e) => {
+            error!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_text(text: &str) -> Result<Weekday, String> {
+        solve(&mut Input::new(text.as_bytes())).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve_text("1\nTuesday Mar 12\nJan 1\n"), Ok(Weekday::Monday));
+        assert_eq!(solve_text("0\nsunday december 31\nJAN 1\n"), Ok(Weekday::Sunday));
+        assert_eq!(solve_text("0\nMonday Feb 29\nJan 1\n"), Err("Feb has no day 29 in a common year".to_string()));
+        assert_eq!(solve_text("0\nMonday Fev 2\nJan 1\n"),
+                   Err("line 2 \"Monday Fev 2\": couldn't parse \"Fev\": unknown month 'Fev'".to_string()));
+    }
+}
@@ -0,0 +1,34 @@
+use codingame::debug;
+use codingame::input::Input;
+use codingame::pathing::{Map, Walker};
+
+const WIDTH: i32 = 40;
+const HEIGHT: i32 = 18;
+
+/**
+ * Auto-generated code below aims at helping you parse
+ * the standard input according to the problem statement.
+ * ---
+ * Hint: You can use the debug stream to print initialTX and initialTY, if Thor seems not follow your orders.
+ **/
+fn main() {
+    let mut input = Input::stdin();
+    // light_x: the X position of the light of power
+    // light_y: the Y position of the light of power
+    // initial_tx: Thor's starting X position
+    // initial_ty: Thor's starting Y position
+    let (light_x, light_y, initial_tx, initial_ty) = input.parse::<(i32, i32, i32, i32)>();
+    debug!("light at ({}, {}), Thor at ({}, {})", light_x, light_y, initial_tx, initial_ty);
+
+    // Thor is only told where he starts, so he has to keep count himself
+    let mut thor = Walker::new(Map::new(WIDTH, HEIGHT), (initial_tx, initial_ty));
+    // game loop
+    while let Ok(remaining_turns) = input.try_parse::<u32>() { // The remaining amount of turns Thor can move. Do not remove this line.
+        let dir = match thor.step_toward((light_x, light_y), remaining_turns) {
+            Some(dir) => dir,
+            None => break,
+        };
+        // A single line providing the move to be made: N NE E SE S SW W or NW
+        println!("{}", dir);
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use codingame::debug;
+use codingame::direction::{Direction, Point};
+use codingame::input::Input;
+use codingame::pathing::{distance, Map};
+
+const WIDTH: i32 = 40;
+const HEIGHT: i32 = 18;
+// A strike wipes out every giant within this many cells along both axes.
+const STRIKE_RANGE: i32 = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Action {
+    Strike,
+    /// A move, or waiting with `Direction::Wait`.
+    Move(Direction),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Action::Strike => write!(f, "STRIKE"),
+            Action::Move(dir) => write!(f, "{}", dir),
+        }
+    }
+}
+
+// Where Thor stands after `action`, or nothing if it would take him off the map.
+fn destination(thor: Point, action: Action) -> Option<Point> {
+    let pos = match action {
+        Action::Strike => thor,
+        Action::Move(dir) => dir.step(thor),
+    };
+    if Map::new(WIDTH, HEIGHT).contains(pos) { Some(pos) } else { None }
+}
+
+// Where the giants stand once they've all stepped toward Thor at `thor`.
+fn close_in(thor: Point, giants: &[Point]) -> Vec<Point> {
+    giants.iter().map(|&giant| Direction::toward(giant, thor).step(giant)).collect()
+}
+
+// The actions that leave Thor on the map and out of reach, as (action, where he ends up). A
+//  giant next to Thor's spot would step onto it.
+fn safe_actions(thor: Point, giants: &[Point]) -> Vec<(Action, Point)> {
+    Some(Direction::Wait).iter()
+        .chain(&Direction::ALL)
+        .map(|&dir| Action::Move(dir))
+        .filter_map(|action| destination(thor, action).map(|pos| (action, pos)))
+        .filter(|&(_, pos)| giants.iter().all(|&giant| distance(pos, giant) > 1))
+        .collect()
+}
+
+// Strikes once the giants in range are at least their share of the `strikes` left, so that
+//  the strikes to come can still deal with the rest: with one strike left that means every
+//  giant, and with none it never strikes. Also strikes, if it can, when there's nowhere safe
+//  left to stand. Until then, keeps the pack as tight as it can around Thor, so that each
+//  strike takes out as many as possible: of the safe spots, the one with the farthest giant
+//  nearest, preferring spots that leave a way out next turn, then more ways out, then waiting.
+fn choose_action(thor: Point, giants: &[Point], strikes: i32) -> Action {
+    if strikes > 0 {
+        let in_range = giants.iter().filter(|&&giant| distance(thor, giant) <= STRIKE_RANGE).count();
+        if in_range > 0 && in_range * strikes as usize >= giants.len() {
+            return Action::Strike;
+        }
+    }
+
+    let best = safe_actions(thor, giants).into_iter().min_by_key(|&(_, pos)| {
+        let giants = close_in(pos, giants);
+        let ways_out = safe_actions(pos, &giants).len();
+        let farthest = giants.iter().map(|&giant| distance(pos, giant)).max().unwrap();
+        (ways_out == 0, farthest, usize::MAX - ways_out)
+    });
+    match best {
+        Some((action, _)) => action,
+        None if strikes > 0 => Action::Strike,
+        None => Action::Move(Direction::Wait),
+    }
+}
+
+/**
+ * Thor makes his first appearance in a small village where giants are trying to crush him...
+ **/
+fn main() {
+    let mut input = Input::stdin();
+    let mut thor = input.parse::<(i32, i32)>(); // Thor's starting position
+
+    // game loop
+    // h: the remaining number of hammer strikes
+    // n: the number of giants which are still present on the map
+    while let Ok((h, n)) = input.try_parse::<(i32, usize)>() {
+        let giants = (0..n).map(|_| input.parse::<(i32, i32)>()).collect::<Vec<_>>();
+
+        let action = choose_action(thor, &giants, h);
+        debug!("Thor at {:?} with {} strikes, {} giants: {}", thor, h, n, action);
+        thor = destination(thor, action).unwrap();
+
+        // The movement or action to be carried out: WAIT STRIKE N NE E SE S SW W or N
+        println!("{}", action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_action() {
+        // everything in range
+        assert_eq!(choose_action((10, 10), &[(6, 14), (14, 6)], 1), Action::Strike);
+        // surrounded
+        assert_eq!(choose_action((0, 0), &[(1, 1), (2, 0), (0, 2), (30, 10)], 1), Action::Strike);
+        // nothing close, so head for the pack
+        let action = choose_action((10, 10), &[(20, 10), (22, 10)], 1);
+        assert_eq!(destination((10, 10), action).unwrap().0, 11, "{}", action);
+        assert_eq!(choose_action((10, 10), &[(20, 10), (0, 10)], 1), Action::Move(Direction::Wait));
+        // but never next to a giant
+        let giants = [(12, 10), (30, 10)];
+        let action = choose_action((10, 10), &giants, 1);
+        assert!(safe_actions((10, 10), &giants).iter().any(|&(safe, _)| safe == action), "{}", action);
+        assert_ne!(action.to_string(), "E");
+    }
+
+    #[test]
+    fn test_several_strikes() {
+        // two of four giants in range is their share of two strikes, one isn't
+        let giants = [(12, 10), (10, 13), (30, 2), (32, 2)];
+        assert_eq!(choose_action((10, 10), &giants, 2), Action::Strike);
+        assert_ne!(choose_action((10, 10), &giants[1..], 2), Action::Strike);
+        assert_eq!(choose_action((10, 10), &giants[1..], 3), Action::Strike);
+        // nothing in range is never worth a strike
+        assert_ne!(choose_action((10, 10), &[(30, 2)], 5), Action::Strike);
+    }
+
+    #[test]
+    fn test_last_strike() {
+        // with the last strike, three of four giants in range isn't enough
+        let giants = [(12, 10), (10, 13), (7, 7), (32, 2)];
+        let action = choose_action((10, 10), &giants, 1);
+        assert_ne!(action, Action::Strike);
+        assert!(safe_actions((10, 10), &giants).iter().any(|&(safe, _)| safe == action), "{}", action);
+        assert_eq!(choose_action((10, 10), &giants[..3], 1), Action::Strike);
+    }
+
+    #[test]
+    fn test_no_strikes() {
+        assert_ne!(choose_action((10, 10), &[(6, 14), (14, 6)], 0), Action::Strike);
+        // even surrounded
+        assert_eq!(choose_action((0, 0), &[(1, 1), (2, 0), (0, 2)], 0), Action::Move(Direction::Wait));
+    }
+
+    #[test]
+    fn test_safe_actions() {
+        let actions = safe_actions((0, 0), &[(2, 0)]).into_iter().map(|(a, _)| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(actions, vec!["WAIT", "S"]);
+        assert_eq!(close_in((0, 0), &[(2, 0), (5, 5), (0, 1)]), vec![(1, 0), (4, 4), (0, 0)]);
+    }
+}
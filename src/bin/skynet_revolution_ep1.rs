@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+
+use codingame::debug;
+use codingame::skynet;
+use codingame::skynet::{Graph, gateway_distances, any_gateway_link};
+#[cfg(test)]
+use codingame::skynet::{read_level, sever, agent_step};
+#[cfg(test)]
+use codingame::input::Input;
+
+fn main() {
+    skynet::run(choose_link, nearest_gateway_path);
+}
+
+// The last link on the agent's shortest path to its nearest gateway, as (gateway, node).
+//  Cutting at the gateway end means the cut never has to be made again from another side.
+fn choose_link(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> (i32, i32) {
+    let path = nearest_gateway_path(si, graph, gateways);
+    debug!("path to the nearest gateway: {:?}", path);
+    match path.len() {
+        // the agent is cut off already, so any remaining gateway link will do
+        0 => any_gateway_link(graph, gateways).expect("no gateway links left to sever"),
+        len => (path[len - 1], path[len - 2]),
+    }
+}
+
+// The agent's shortest path to its nearest gateway, from `si` to the gateway inclusive, or
+//  nothing if it can't reach one.
+fn nearest_gateway_path(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> Vec<i32> {
+    let nearest = gateways.iter()
+                          .filter_map(|&gw| {
+                              let dists = gateway_distances(gw, graph, gateways);
+                              dists.get(&si).cloned().map(|dist| (dist, gw, dists))
+                          })
+                          .min_by_key(|&(dist, gw, _)| (dist, gw));
+
+    let (dist, gw, dists) = match nearest {
+        Some(found) => found,
+        None => return Vec::new(),
+    };
+
+    // walk down the distance map towards the gateway
+    let mut path = vec![si];
+    for d in (1..dist).rev() {
+        let currid = *graph.get(path.last().unwrap()).unwrap()
+                          .borrow()
+                          .nbrs.iter()
+                          .filter(|&nbr| dists.get(nbr) == Some(&d))
+                          .min()
+                          .unwrap();
+        path.push(currid);
+    }
+    path.push(gw);
+
+    path
+}
+
+#[cfg(test)]
+const SIMPLE_LEVEL: &str = "3 2 1
+1 2
+1 0
+2
+";
+
+#[cfg(test)]
+const DOUBLE_PATH_LEVEL: &str = "4 4 1
+1 3
+2 3
+0 1
+0 2
+3
+";
+
+#[cfg(test)]
+const STAR_LEVEL: &str = "12 23 1
+11 6
+0 9
+1 2
+0 1
+10 1
+11 5
+2 3
+4 5
+8 9
+6 7
+7 8
+0 6
+3 4
+0 2
+11 7
+0 8
+0 4
+9 10
+0 5
+0 7
+0 3
+0 10
+5 6
+0
+";
+
+// Plays the agent against `choose_link`, the agent moving as `agent_step` has it. Returns
+//  whether the agent was kept out of every gateway.
+#[cfg(test)]
+fn play_out(level: &str, mut si: i32, max_turns: usize) -> bool {
+    let (graph, gateways) = read_level(&mut Input::new(level.as_bytes()));
+
+    for _ in 0..max_turns {
+        let (gw, target) = choose_link(si, &graph, &gateways);
+        assert!(graph.get(&gw).unwrap().borrow().nbrs.contains(&target));
+        sever(&gw, &target, &graph, &mut Vec::new());
+
+        match agent_step(si, &graph, &gateways) {
+            Some(node) if gateways.contains(&node) => return false,
+            Some(node) => si = node,
+            None => return true,
+        }
+    }
+    true
+}
+
+#[test]
+fn test_choose_link() {
+    let (graph, gateways) = read_level(&mut Input::new(SIMPLE_LEVEL.as_bytes()));
+    assert_eq!(choose_link(1, &graph, &gateways), (2, 1));
+    assert_eq!(nearest_gateway_path(0, &graph, &gateways), vec![0, 1, 2]);
+
+    let (graph, gateways) = read_level(&mut Input::new(DOUBLE_PATH_LEVEL.as_bytes()));
+    assert_eq!(choose_link(0, &graph, &gateways), (3, 1));
+    sever(&3, &1, &graph, &mut Vec::new());
+    assert_eq!(choose_link(0, &graph, &gateways), (3, 2));
+}
+
+#[test]
+fn test_sample_levels() {
+    assert!(play_out(SIMPLE_LEVEL, 1, 10));
+    assert!(play_out(DOUBLE_PATH_LEVEL, 0, 10));
+    assert!(play_out(STAR_LEVEL, 11, 30));
+}
@@ -0,0 +1,623 @@
+use std::collections::{HashSet, HashMap, VecDeque, BinaryHeap};
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+
+use codingame::debug;
+use codingame::skynet;
+use codingame::skynet::{Graph, gateway_distances, any_gateway_link};
+#[cfg(test)]
+use codingame::skynet::{read_level, sever, agent_step, level_links, dot_frame, GameLog, play, replay, replay_with_frames};
+#[cfg(test)]
+use codingame::input::Input;
+#[cfg(test)]
+use std::io;
+
+#[derive(PartialEq,PartialOrd,Debug)]
+ struct PFInfo {
+     avg: f32,
+     cum: i32,
+     dist: i32,
+     prev: Option<i32>,
+ }
+
+impl Eq for PFInfo {
+    //  fn eq(&self, other: &PFInfo) -> bool {
+    //     match self.partial_eq(other) {
+    //         Some(eq) => eq,
+    //         None => false
+    //     }
+    // }
+}
+
+// Orders by the derived `PartialOrd`, treating incomparable `avg`s as equal.
+#[allow(clippy::derive_ord_xor_partial_ord)]
+impl Ord for PFInfo {
+     fn cmp(&self, other: &PFInfo) -> Ordering {
+        match self.partial_cmp(other) {
+            Some(ord) => ord,
+            None => Ordering::Equal
+        }
+    }
+}
+
+ type PFGraph = HashMap<i32, PFInfo>;
+
+// Danger scores for a node, taken along the agent's shortest path to it.
+//  `cum` counts the gateway links of every node on that path (this one included) and
+//  `slack` is `dist - cum`: the number of spare cuts we have before the agent gets there.
+//  A negative slack means one of this node's links has to go this turn.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+ struct DangerScore {
+     dist: i32,
+     cum: i32,
+     slack: i32,
+ }
+
+ struct DangerMap {
+     gw_dists: HashMap<i32, HashMap<i32, i32>>,
+     scores: HashMap<i32, DangerScore>,
+ }
+
+// A node linked to two or more gateways. `free_turns` counts the nodes without gateway
+//  links the agent has to cross to get there, ie the turns where we aren't forced to cut
+//  somewhere else first. With none, the agent reaches it through a chain of
+//  gateway-adjacent nodes and it has to be dealt with now.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+ struct DoubleLink {
+     node: i32,
+     free_turns: i32,
+     dist: i32,
+ }
+
+fn main() {
+    skynet::run(choose_link, |si, graph, _| generate_path(&si, graph));
+}
+
+fn choose_link(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> (i32, i32) {
+    let danger = DangerMap::new(si, graph, gateways);
+    debug!("{}", danger);
+    let doubles = find_double_links(si, graph, gateways);
+    debug!("double links: {:?}", doubles);
+
+    let target = if graph.get(&si).unwrap()
+                  .borrow()
+                  .nbrs.iter()
+                  .any(|nbr| gateways.contains(nbr)) {
+        si
+    } else if let Some(emergency) = doubles.iter().find(|x| x.free_turns == 0) {
+        emergency.node
+    } else {
+        match danger.most_urgent(graph) {
+            Some((node, score)) if score.slack < 0 => node,
+            _ => {
+                let path = generate_path(&si, graph);
+                *choose_target( &path, graph )
+            }
+        }
+    };
+
+    let gw = graph.get(&target).unwrap()
+                  .borrow()
+                  .nbrs.iter()
+                  .filter(|x| gateways.contains(x))
+                  .min()
+                  .cloned();
+
+    match gw {
+        Some(gw) => (gw, target),
+        // nothing the agent can reach is linked to a gateway any more
+        None => any_gateway_link(graph, gateways).expect("no gateway links left to sever"),
+    }
+}
+
+impl DangerMap {
+    fn new(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> Self {
+        let gw_dists = gateways.iter()
+                               .map(|&gw| (gw, gateway_distances(gw, graph, gateways)))
+                               .collect();
+
+        // BFS out from the agent, keeping the most gateway links over all the shortest
+        //  paths to a node. Every predecessor of a node sits one layer up, so they've all
+        //  been popped by the time the node itself is.
+        let mut scores: HashMap<i32, DangerScore> = HashMap::with_capacity(graph.len());
+        let mut queue = VecDeque::new();
+        let cum = graph.get(&si).unwrap().borrow().gwlinks;
+        scores.insert(si, DangerScore { dist: 0, cum, slack: -cum });
+        queue.push_back(si);
+
+        while let Some(currid) = queue.pop_front() {
+            let curr = *scores.get(&currid).unwrap();
+            for &nbrid in graph.get(&currid).unwrap()
+                               .borrow()
+                               .nbrs.iter()
+                               .filter(|x| !gateways.contains(x)) {
+                let dist = curr.dist + 1;
+                let cum = curr.cum + graph.get(&nbrid).unwrap().borrow().gwlinks;
+                match scores.get(&nbrid).cloned() {
+                    None => {
+                        scores.insert(nbrid, DangerScore { dist, cum, slack: dist - cum });
+                        queue.push_back(nbrid);
+                    },
+                    Some(ref old) if old.dist == dist && old.cum < cum => {
+                        scores.insert(nbrid, DangerScore { dist, cum, slack: dist - cum });
+                    },
+                    Some(_) => {}
+                }
+            }
+        }
+
+        DangerMap { gw_dists, scores }
+    }
+
+    // The gateway-linked node with the least slack, nearest first on ties.
+    fn most_urgent(&self, graph: &Graph) -> Option<(i32, DangerScore)> {
+        self.scores.iter()
+                   .filter(|&(id, _)| graph.get(id).unwrap().borrow().gwlinks > 0)
+                   .min_by_key(|&(&id, score)| (score.slack, score.dist, id))
+                   .map(|(&id, &score)| (id, score))
+    }
+}
+
+impl fmt::Display for DangerMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut gws = self.gw_dists.keys().cloned().collect::<Vec<_>>();
+        gws.sort();
+        let mut nodes = self.scores.keys().cloned().collect::<Vec<_>>();
+        nodes.sort();
+
+        write!(f, "node: dist cum slack |")?;
+        for gw in &gws {
+            write!(f, " gw{}", gw)?;
+        }
+        for node in &nodes {
+            let score = self.scores.get(node).unwrap();
+            write!(f, "\n{}: {} {} {} |", node, score.dist, score.cum, score.slack)?;
+            for gw in &gws {
+                match self.gw_dists.get(gw).unwrap().get(node) {
+                    Some(d) => write!(f, " {}", d)?,
+                    None => write!(f, " -")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Every node linked to two or more gateways that the agent can still reach, fewest free turns
+//  first. Searches on (free turns, distance), so a longer chain of gateway-adjacent nodes wins
+//  out over a shorter path that gives us time to cut.
+fn find_double_links(si: i32, graph: &Graph, gateways: &HashSet<i32>) -> Vec<DoubleLink> {
+    let mut best: HashMap<i32, (i32, i32)> = HashMap::with_capacity(graph.len());
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0, 0, si)));
+
+    while let Some(Reverse((free_turns, dist, currid))) = heap.pop() {
+        if best.contains_key(&currid) {
+            continue;
+        }
+        best.insert(currid, (free_turns, dist));
+
+        for &nbrid in graph.get(&currid).unwrap()
+                           .borrow()
+                           .nbrs.iter()
+                           .filter(|x| !gateways.contains(x) && !best.contains_key(x)) {
+            let nbr_free = if graph.get(&nbrid).unwrap().borrow().gwlinks > 0 { 0 } else { 1 };
+            heap.push(Reverse((free_turns + nbr_free, dist + 1, nbrid)));
+        }
+    }
+
+    let mut doubles = best.iter()
+                          .filter(|&(id, _)| graph.get(id).unwrap().borrow().gwlinks >= 2)
+                          .map(|(&id, &(free_turns, dist))| DoubleLink { node: id, free_turns, dist })
+                          .collect::<Vec<_>>();
+    doubles.sort_by_key(|x| (x.free_turns, x.dist, x.node));
+    doubles
+}
+
+fn generate_path(si: &i32, graph: &Graph) -> Vec<i32> {
+    let n = graph.len();
+    let mut boundary = PFGraph::with_capacity(n);
+    let mut finished = PFGraph::with_capacity(n);
+
+    boundary.insert( *si,
+                     PFInfo {
+                         avg: 0.,
+                         cum: graph.get(si).unwrap().borrow().gwlinks,
+                         dist: 0,
+                         prev: None } );
+
+    while !boundary.is_empty() {
+        // ties go to the lowest id so that replays of a game make the same decisions
+        let currid = *boundary.iter().max_by_key(|x| (x.1, Reverse(*x.0))).unwrap().0;
+        let val = boundary.remove(&currid).unwrap();
+        finished.insert(currid, val);
+
+        for nbrid in graph.get(&currid).unwrap()
+                          .borrow()
+                          .nbrs.iter()
+                          .filter(|x| !finished.contains_key(x)) {
+            let currval = finished.get(&currid).unwrap();
+            let prev = Some(currid);
+            let dist = currval.dist + 1;
+            let cum = currval.cum + graph.get(nbrid).unwrap().borrow().gwlinks;
+            let avg = cum as f32 / dist as f32;
+            boundary.insert(
+                *nbrid,
+                PFInfo {
+                    avg,
+                    cum,
+                    dist,
+                    prev,
+                }
+            );
+        }
+    }
+    // println!("PF data populated");
+
+    // while let Some( val ) = boundary.iter().max_by_key(|x| x.1).and_then(|item| boundary.remove(item.0)) {
+    //     let currid = &1;
+    //     finished.insert(currid.clone(), val);
+    //
+    //     for nbrid in graph.get(&currid).unwrap()
+    //                       .borrow()
+    //                       .nbrs.iter()
+    //                       .filter(|x| !finished.contains_key(x)) {
+    //         let currval = finished.get(currid).unwrap();
+    //         let prev = Some(currid.clone());
+    //         let dist = currval.dist + 1;
+    //         let cum = currval.cum + graph.get(nbrid).unwrap().borrow().gwlinks;
+    //         let avg = cum as f32 / dist as f32;
+    //         boundary.insert(
+    //             nbrid.clone(),
+    //             PFInfo {
+    //                 avg: avg,
+    //                 cum: cum,
+    //                 dist: dist,
+    //                 prev: prev,
+    //             }
+    //         );
+    //     }
+    // }
+
+    // while let Some( (currid, _) ) = boundary.iter().max_by_key(|x| x.1) {
+    //     let val = boundary.remove(currid).unwrap();
+    //     finished.insert(currid.clone(), val);
+    //
+    //     for nbrid in graph.get(&currid).unwrap()
+    //                       .borrow()
+    //                       .nbrs.iter()
+    //                       .filter(|x| !finished.contains_key(x)) {
+    //         let currval = finished.get(currid).unwrap();
+    //         let prev = Some(currid.clone());
+    //         let dist = currval.dist + 1;
+    //         let cum = currval.cum + graph.get(nbrid).unwrap().borrow().gwlinks;
+    //         let avg = cum as f32 / dist as f32;
+    //         boundary.insert(
+    //             nbrid.clone(),
+    //             PFInfo {
+    //                 avg: avg,
+    //                 cum: cum,
+    //                 dist: dist,
+    //                 prev: prev,
+    //             }
+    //         );
+    //     }
+    // }
+
+    // println!("backtracking beginning");
+    let mut path = Vec::new();
+    let mut currid = *finished.iter().max_by_key(|x| (x.1, Reverse(*x.0))).unwrap().0;
+    path.push(currid);
+    while let Some(previd) = finished.get(&currid).unwrap().prev {
+        path.push(previd);
+        currid = previd;
+    }
+    path.reverse();
+
+    path
+}
+
+fn choose_target<'a>(path: &'a [i32], graph: &Graph) -> &'a i32 {
+    // find the earliest node on the path with the highest number of gw links
+    // `rev` is needed because `max_by_key` returns the LAST found maximum and we need the
+    // FIRST found
+    path.iter().rev().max_by_key(|x| graph.get(x).unwrap().borrow().gwlinks).unwrap()
+}
+
+#[cfg(test)]
+const TEST_LEVEL: &str = "8 13 2
+6 2
+7 3
+6 3
+5 3
+3 4
+7 1
+2 0
+0 1
+0 3
+1 3
+2 3
+7 4
+6 5
+4
+5
+";
+
+#[test]
+fn test_generate_path() {
+    let (graph, _) = read_level(&mut Input::new(TEST_LEVEL.as_bytes()));
+
+    assert_eq!(generate_path(&0, &graph), vec![0, 3]);
+}
+
+#[test]
+fn test_danger_map() {
+    let (graph, gateways) = read_level(&mut Input::new(TEST_LEVEL.as_bytes()));
+    let danger = DangerMap::new(0, &graph, &gateways);
+
+    assert_eq!(danger.gw_dists.get(&4).unwrap().get(&3), Some(&1));
+    assert_eq!(danger.gw_dists.get(&4).unwrap().get(&0), Some(&2));
+    assert_eq!(danger.gw_dists.get(&5).unwrap().get(&0), Some(&2));
+    assert_eq!(danger.gw_dists.get(&5).unwrap().get(&4), None);
+
+    // 3 is linked to both gateways, and the agent is one step away from it
+    assert_eq!(danger.scores.get(&3), Some(&DangerScore { dist: 1, cum: 2, slack: -1 }));
+    assert_eq!(danger.most_urgent(&graph), Some((3, DangerScore { dist: 1, cum: 2, slack: -1 })));
+    assert!(!danger.scores.contains_key(&4));
+}
+
+// 0 can reach 3 in two moves through 5, which leaves a turn to spare, but the longer way
+//  round through 1 and 2 forces a cut every turn.
+#[cfg(test)]
+const CHAIN_LEVEL: &str = "10 9 2
+0 1
+1 2
+2 3
+0 5
+5 3
+1 9
+2 9
+3 9
+3 8
+8
+9
+";
+
+#[test]
+fn test_double_links() {
+    let (graph, gateways) = read_level(&mut Input::new(CHAIN_LEVEL.as_bytes()));
+
+    assert_eq!(find_double_links(0, &graph, &gateways),
+               vec![DoubleLink { node: 3, free_turns: 0, dist: 3 }]);
+    assert_eq!(DangerMap::new(0, &graph, &gateways).most_urgent(&graph).unwrap().1.slack, 0);
+    assert_eq!(choose_link(0, &graph, &gateways), (8, 3));
+
+    // in the sample level 3 is right next to the agent
+    let (graph, gateways) = read_level(&mut Input::new(TEST_LEVEL.as_bytes()));
+    assert_eq!(find_double_links(0, &graph, &gateways),
+               vec![DoubleLink { node: 3, free_turns: 0, dist: 1 }]);
+}
+
+#[test]
+fn test_record_and_replay() {
+    let input = format!("{}0\n2\n", TEST_LEVEL);
+    let mut out = Vec::new();
+    let mut log = Vec::new();
+    play(&mut Input::new(input.as_bytes()), &mut out, &mut log, choose_link);
+    assert_eq!(String::from_utf8(out).unwrap(), "4 3\n5 3\n");
+
+    let mut game = GameLog::read(&log[..]).unwrap();
+    assert_eq!(game.level.len(), 16);
+    assert_eq!(game.turns, vec![(0, "4 3".to_string()), (2, "5 3".to_string())]);
+
+    assert!(replay(&game, choose_link).is_empty());
+    game.turns[1].1 = "6 2".to_string();
+    assert_eq!(replay(&game, choose_link), vec!["turn 2 (agent at 2): recorded \"6 2\", got \"5 3\""]);
+}
+
+#[test]
+fn test_frames_only_when_asked() {
+    let game = GameLog::read(format!("{}0\n> 4 3\n", TEST_LEVEL).as_bytes()).unwrap();
+    assert!(replay_with_frames(&game, None, choose_link, |_, _, _| panic!("path worked out without frames")).is_empty());
+
+    let mut frames = Vec::new();
+    assert!(replay_with_frames(&game, Some(&mut frames), choose_link, |si, graph, _| generate_path(&si, graph)).is_empty());
+    let frames = String::from_utf8(frames).unwrap();
+    assert!(frames.starts_with("graph turn_1 {\n"));
+    assert!(frames.contains("    4 -- 3 [color=red, penwidth=3];\n") || frames.contains("    3 -- 4 [color=red, penwidth=3];\n"));
+}
+
+#[test]
+fn test_dot_frame() {
+    let (graph, gateways) = read_level(&mut Input::new(TEST_LEVEL.as_bytes()));
+    let links = level_links(TEST_LEVEL);
+    assert_eq!(links.len(), 13);
+    sever(&4, &3, &graph, &mut io::sink());
+
+    let frame = dot_frame(2, &links, &graph, &gateways, 2, (5, 3), &[2, 3]);
+    assert!(frame.starts_with("graph turn_2 {\n"));
+    assert!(frame.contains("    label=\"turn 2: agent at 2, severing 5 3\";\n"));
+    assert!(frame.ends_with("}\n"));
+    assert!(frame.contains("    4 [shape=doublecircle, fillcolor=tomato];\n"));
+    assert!(frame.contains("    2 [fillcolor=gold];\n"));
+    assert!(frame.contains("    3 [fillcolor=lightblue];\n"));
+    assert!(frame.contains("    0;\n"));
+    assert!(frame.contains("    3 -- 4 [style=dashed, color=grey];\n"));
+    assert!(frame.contains("    5 -- 3 [color=red, penwidth=3];\n"));
+    assert!(frame.contains("    2 -- 3 [color=blue, penwidth=2];\n"));
+    assert!(frame.contains("    6 -- 2;\n"));
+}
+
+// Property tests over random levels. Each case is generated from its own seed, which is
+//  printed on failure so it can be replayed on its own.
+
+#[cfg(test)]
+struct XorShift(u64);
+
+#[cfg(test)]
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // uniform-ish in lo..hi
+    fn range(&mut self, lo: i32, hi: i32) -> i32 {
+        lo + (self.next() % (hi - lo) as u64) as i32
+    }
+}
+
+// A random level in the CodinGame format, with the agent's starting node. Gateways
+//  are never linked to each other. With `single_links` no node gets more than one gateway
+//  link, which makes the level winnable whatever the agent does: any node the agent stands
+//  on can be cleared that same turn.
+#[cfg(test)]
+fn random_level(rng: &mut XorShift, single_links: bool) -> (String, i32) {
+    let e = rng.range(1, 4);
+    let n = e + rng.range(3, 27);
+    let gateways = (n - e..n).collect::<Vec<_>>();
+
+    let mut links = HashSet::new();
+    // a random spanning forest over the ordinary nodes, mostly one tree, then a few extra links
+    for node in 1..(n - e) {
+        if rng.range(0, 10) > 0 {
+            links.insert((rng.range(0, node), node));
+        }
+    }
+    for _ in 0..rng.range(0, n) {
+        let (a, b) = (rng.range(0, n - e), rng.range(0, n - e));
+        if a != b {
+            links.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    // each gateway gets one link of its own before the rest are handed out
+    let mut linked = HashSet::new();
+    for &gw in &gateways {
+        let mut node = rng.range(0, n - e);
+        while linked.contains(&node) {
+            node = (node + 1) % (n - e);
+        }
+        linked.insert(node);
+        links.insert((node, gw));
+    }
+    for &gw in &gateways {
+        for _ in 0..rng.range(0, 4) {
+            let node = rng.range(0, n - e);
+            if !single_links || linked.insert(node) {
+                links.insert((node, gw));
+            }
+        }
+    }
+
+    let mut level = format!("{} {} {}\n", n, links.len(), e);
+    let mut links = links.into_iter().collect::<Vec<_>>();
+    links.sort();
+    for (a, b) in links {
+        level.push_str(&format!("{} {}\n", a, b));
+    }
+    for gw in gateways {
+        level.push_str(&format!("{}\n", gw));
+    }
+
+    (level, rng.range(0, n - e))
+}
+
+#[cfg(test)]
+fn assert_gwlinks_consistent(graph: &Graph, gateways: &HashSet<i32>) {
+    for (id, info) in graph {
+        let info = info.borrow();
+        assert_eq!(info.gwlinks, info.nbrs.iter().filter(|x| gateways.contains(x)).count() as i32,
+                   "gwlinks of node {} out of step with its links", id);
+        for nbr in &info.nbrs {
+            assert!(graph.get(nbr).unwrap().borrow().nbrs.contains(id), "link {}-{} is one-sided", id, nbr);
+        }
+    }
+}
+
+#[cfg(test)]
+fn has_gateway_links(graph: &Graph, gateways: &HashSet<i32>) -> bool {
+    gateways.iter().any(|gw| !graph.get(gw).unwrap().borrow().nbrs.is_empty())
+}
+
+#[cfg(test)]
+fn random_agent(rng: &mut XorShift, si: i32, graph: &Graph) -> Option<i32> {
+    let mut nbrs = graph.get(&si).unwrap().borrow().nbrs.iter().cloned().collect::<Vec<_>>();
+    nbrs.sort();
+    if nbrs.is_empty() {
+        None
+    } else {
+        Some(nbrs[rng.range(0, nbrs.len() as i32) as usize])
+    }
+}
+
+// Plays a level out turn by turn, checking the invariants after every cut, until the
+//  gateways are all cut off. Returns false if the agent got into a gateway.
+#[cfg(test)]
+fn check_game(level: &str, mut si: i32, rng: &mut XorShift, greedy: bool) -> bool {
+    let (graph, gateways) = read_level(&mut Input::new(level.as_bytes()));
+    let mut input = level.to_string();
+
+    for turn in 0..50 {
+        if !has_gateway_links(&graph, &gateways) {
+            return true;
+        }
+
+        let (gw, target) = choose_link(si, &graph, &gateways);
+        assert!(gateways.contains(&gw), "turn {}: {} is not a gateway", turn, gw);
+        assert!(graph.get(&gw).unwrap().borrow().nbrs.contains(&target),
+                "turn {}: there is no link {}-{} to sever", turn, gw, target);
+
+        // the same decision has to come out of the real game loop
+        input.push_str(&format!("{}\n", si));
+        let mut out = Vec::new();
+        play(&mut Input::new(input.as_bytes()), &mut out, &mut io::sink(), choose_link);
+        let replies = String::from_utf8(out).unwrap();
+        assert_eq!(replies.lines().last(), Some(format!("{} {}", gw, target).as_str()));
+
+        sever(&gw, &target, &graph, &mut io::sink());
+        assert_gwlinks_consistent(&graph, &gateways);
+
+        let next = if greedy { agent_step(si, &graph, &gateways) } else { random_agent(rng, si, &graph) };
+        // a cut-off agent stays put, and we still have to make a legal cut every turn
+        match next {
+            Some(node) if gateways.contains(&node) => return false,
+            Some(node) => si = node,
+            None => {}
+        }
+    }
+    true
+}
+
+#[test]
+fn prop_random_levels_keep_invariants() {
+    for seed in 1..150u64 {
+        let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let (level, si) = random_level(&mut rng, false);
+        let (graph, gateways) = read_level(&mut Input::new(level.as_bytes()));
+        assert_gwlinks_consistent(&graph, &gateways);
+
+        // the agent may well win these; we only care that every move is legal
+        let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+            check_game(&level, si, &mut rng, false);
+        }));
+        assert!(result.is_ok(), "seed {} failed on level starting at {}:\n{}", seed, si, level);
+    }
+}
+
+#[test]
+fn prop_winnable_levels_are_won() {
+    for seed in 1..150u64 {
+        for &greedy in &[true, false] {
+            let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let (level, si) = random_level(&mut rng, true);
+            assert!(check_game(&level, si, &mut rng, greedy),
+                    "seed {} ({} agent) lost level starting at {}:\n{}",
+                    seed, if greedy { "greedy" } else { "random" }, si, level);
+        }
+    }
+    assert!(check_game(TEST_LEVEL, 0, &mut XorShift(1), true));
+    assert!(check_game(CHAIN_LEVEL, 0, &mut XorShift(1), true));
+}
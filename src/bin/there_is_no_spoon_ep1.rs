@@ -0,0 +1,103 @@
+use codingame::debug;
+use codingame::direction::Point;
+use codingame::input::Input;
+
+// A node with the nearest nodes to its right and below it, if there are any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Node {
+    pos: Point,
+    right: Option<Point>,
+    below: Option<Point>,
+}
+
+// Every node in `matrix`, in reading order. One pass back from the bottom-right corner keeps
+//  the nearest node seen so far in the current row and in each column, which are the
+//  neighbours of the next node found. Rows can be of different lengths; missing cells are empty.
+fn nodes(matrix: &[Vec<char>]) -> Vec<Node> {
+    let width = matrix.iter().map(Vec::len).max().unwrap_or(0);
+    let mut below = vec![None; width];
+    let mut nodes = Vec::new();
+    for (y, row) in matrix.iter().enumerate().rev() {
+        let mut right = None;
+        for (x, &cell) in row.iter().enumerate().rev() {
+            if cell == '0' {
+                let pos = (x as i32, y as i32);
+                nodes.push(Node { pos, right, below: below[x] });
+                right = Some(pos);
+                below[x] = Some(pos);
+            }
+        }
+    }
+    nodes.reverse();
+    nodes
+}
+
+// A point as the answer writes it, `-1 -1` for no node.
+fn coords(point: Option<Point>) -> String {
+    let (x, y) = point.unwrap_or((-1, -1));
+    format!("{} {}", x, y)
+}
+
+/**
+ * Don't let the machines win. You are humanity's last hope...
+ **/
+fn main() {
+    let mut input = Input::stdin();
+
+    let width = input.parse::<usize>(); // the number of cells on the X axis
+    let height = input.parse::<usize>(); // the number of cells on the Y axis
+
+    // width characters, each either 0 or ., though a row can come short of it
+    let matrix = (0..height).map(|_| input.read_line().chars().collect()).collect::<Vec<Vec<char>>>();
+
+    let nodes = nodes(&matrix);
+    debug!("{} nodes on a {}x{} grid", nodes.len(), width, height);
+
+    // Three coordinates: a node, its right neighbor, its bottom neighbor
+    for node in nodes {
+        println!("{} {} {}", coords(Some(node.pos)), coords(node.right), coords(node.below));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codingame::direction::Direction;
+    use codingame::grid::Grid;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn test_nodes() {
+        let nodes = nodes(&grid(&["0.0", ".0.", "0.0"]));
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes[0], Node { pos: (0, 0), right: Some((2, 0)), below: Some((0, 2)) });
+        assert_eq!(nodes[2], Node { pos: (1, 1), right: None, below: None });
+        assert_eq!(nodes[4], Node { pos: (2, 2), right: None, below: None });
+        assert_eq!(coords(nodes[2].right), "-1 -1");
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let nodes = nodes(&grid(&["0", "..0", "", "000"]));
+        let positions = nodes.iter().map(|node| node.pos).collect::<Vec<_>>();
+        assert_eq!(positions, vec![(0, 0), (2, 1), (0, 3), (1, 3), (2, 3)]);
+        assert_eq!(nodes[0].below, Some((0, 3)));
+        assert_eq!(nodes[1].below, Some((2, 3)));
+        assert_eq!(nodes[2].right, Some((1, 3)));
+        assert!(super::nodes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_nodes_match_casting() {
+        let matrix = grid(&["00.0.", ".0..0", "0.000", "...0.", "0.0.0"]);
+        let board = Grid::from_rows(matrix.clone()).unwrap();
+        let cast = |pos, dir| board.cast(pos, dir, |&c| c == '0').map(|(hit, _)| hit);
+        for node in nodes(&matrix) {
+            assert_eq!(node.right, cast(node.pos, Direction::E), "{:?}", node);
+            assert_eq!(node.below, cast(node.pos, Direction::S), "{:?}", node);
+        }
+    }
+}
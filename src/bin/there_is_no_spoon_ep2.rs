@@ -1,55 +1,17 @@
-use std::io;
 use std::cell::RefCell;
-use std::str::FromStr;
 use std::cmp;
 use std::fmt;
-use std::fmt::{Debug, Display};
-use std::fmt::Write as FmtWrite;
-use std::io::Write as IoWrite;
-use std::error::Error;
+use std::fmt::Display;
+use std::mem;
 // use std::{thread, time};
 use std::rc::{Rc, Weak};
 use std::collections::HashSet;
 
+use codingame::direction::Direction;
+use codingame::grid::Grid;
+use codingame::input::Input;
+use codingame::{debug, trace};
 
-// macro_rules! print_err {
-//     ($($arg:tt)*) => (
-//         {
-//             use std::io::Write;
-//             writeln!(&mut ::std::io::stderr(), $($arg)*).ok();
-//         }
-//     )
-// }
-
-fn debug(msg: &str) {
-    // writeln!(&mut ::std::io::stderr(), "{}", msg).ok();
-    println!("{}", msg);
-}
-fn info(msg: &str) {
-    writeln!(&mut ::std::io::stderr(), "{}", msg).ok();
-}
-
-
-
-fn parse_line<T>() -> Result<T, Box<Error>>
-    where T: FromStr, <T as FromStr>::Err: 'static + Debug + Error,
-{
-    let mut input_line = String::new();
-    io::stdin().read_line(&mut input_line).unwrap();
-    let res = input_line.trim().parse::<T>()?;
-    Ok(res)
-}
-
-fn read_line() -> String {
-    let mut input_line = String::new();
-    io::stdin().read_line(&mut input_line).unwrap();
-    input_line.trim_right().to_string()
-}
-
-
-enum HashiError {
-    InitError(String),
-}
 
 struct HashiGraph {
     nodes: Vec<Rc<Node>>,
@@ -68,7 +30,6 @@ type NdRef = Weak<Node>;
 type ID = usize;
 
 struct Node {
-    graph: *mut HashiGraph,
     coords: (u32, u32),
     quota: u32,
     id: ID,
@@ -79,7 +40,6 @@ struct Node {
 }
 
 struct Bridge {
-    graph: *mut HashiGraph,
     domain: RefCell<Domain>,
     nodes: (NdRef, NdRef),
     crosses: RefCell<Vec<BrRef>>,
@@ -131,7 +91,7 @@ trait HashiDomain {
 }
 
 impl HashiGraph {
-    fn new<T: AsRef<str>>(puzzle: &[T]) -> Self {
+    fn new(puzzle: &Grid<char>) -> Self {
         let mut graph = HashiGraph {
             nodes: Vec::new(),
             bridges: Vec::new(),
@@ -139,83 +99,75 @@ impl HashiGraph {
             node_queue: Vec::new(),
         };
 
-        if puzzle.is_empty() {
+        if puzzle.height() == 0 {
             panic!("puzzle is empty");
         }
 
-        let height = puzzle.len();
-        let width = puzzle[0].as_ref().len();
+        let (width, height) = (puzzle.width(), puzzle.height());
 
-        if !puzzle.iter().all(|row| row.as_ref().len() == width) {
-            panic!("puzzle is not rectangular");
-        }
-
-        let mut oldys: Vec<NdRef> = vec![Weak::new(); width]; // Weak::new() always holds None
+        // where each node is, to look up the neighbours found by casting left and up from a new one
+        let mut node_at: Grid<NdRef> = Grid::new(width, height, Weak::new());
+        let is_node = |c: &char| c.is_ascii_digit();
         let mut horz_bridges: Vec<Vec<Rc<Bridge>>> = vec![Vec::with_capacity(width); height];
         let mut vert_bridges: Vec<Vec<Rc<Bridge>>> = vec![Vec::with_capacity(height); width];
-        for (y, ref_to_line) in puzzle.iter().enumerate() {
-            let line = ref_to_line.as_ref();
-            let mut oldx: NdRef = Weak::new(); // Weak::new() always holds None
-
-            for (x, puzz_char) in line.chars().enumerate() {
-                if let Some(number) = puzz_char.to_digit(10) {
-                    let new_node: NdRef = graph._add_node((x as u32, y as u32), number);
-                    // debug("node!");
-
-                    if let Some(oldx_node_rc) = oldx.upgrade() {
-                        // debug("horz!");
-                        let new_bridge: BrRef = graph._add_bridge((Rc::downgrade(&oldx_node_rc), new_node.clone()));
-                        let new_node_rc = new_node.upgrade().unwrap();
-                        let new_bridge_rc = new_bridge.upgrade().unwrap();
-
-                        horz_bridges[y].push(new_bridge_rc.clone());
-
-                        *new_node_rc.lf.borrow_mut() = new_bridge.clone();
-                        *oldx_node_rc.rt.borrow_mut() = new_bridge.clone();
-                    }
+        for (pos, puzz_char) in puzzle.cells() {
+            if let Some(number) = puzz_char.to_digit(10) {
+                let (x, y) = (pos.0 as usize, pos.1 as usize);
+                let new_node: NdRef = graph._add_node((x as u32, y as u32), number);
+                node_at[pos] = new_node.clone();
+                trace!("node {} at ({}, {}) wants {}", graph.nodes.len() - 1, x, y, number);
+
+                if let Some((left, _)) = puzzle.cast(pos, Direction::W, is_node) {
+                    let oldx_node_rc = node_at[left].upgrade().unwrap();
+                    let new_bridge: BrRef = graph._add_bridge((Rc::downgrade(&oldx_node_rc), new_node.clone()));
+                    let new_node_rc = new_node.upgrade().unwrap();
+                    let new_bridge_rc = new_bridge.upgrade().unwrap();
+
+                    horz_bridges[y].push(new_bridge_rc.clone());
+
+                    *new_node_rc.lf.borrow_mut() = new_bridge.clone();
+                    *oldx_node_rc.rt.borrow_mut() = new_bridge.clone();
+                }
 
-                    if let Some(oldy_node_rc) = oldys[x].upgrade() {
-                        // debug("vert!");
-                        let new_bridge: BrRef = graph._add_bridge((Rc::downgrade(&oldy_node_rc), new_node.clone()));
-                        let new_bridge_rc = new_bridge.upgrade().unwrap();
-                        let new_node_rc = new_node.upgrade().unwrap();
-
-                        // debug(&format!("vert_bridges len:{}", oldys.len()));
-                        vert_bridges[x].push(new_bridge_rc.clone());
-
-                        // Check if bridge crosses any previous bridges
-                        for span_y in (oldy_node_rc.coords.1 + 1)..new_node_rc.coords.1 {
-                            for hbridge in horz_bridges.get(span_y as usize).unwrap() {
-                                let (ref n1, ref n2) = hbridge.nodes;
-                                let (x1, x2) = (n1.upgrade().unwrap().coords.0, n2.upgrade().unwrap().coords.0);
-                                let (xa, xb) = if x1 < x2 {(x1, x2)} else {(x2, x1)};
-                                if xa < (x as u32) && (x as u32) < xb {
-                                    new_bridge_rc.crosses.borrow_mut().push(Rc::downgrade(&hbridge));
-                                    hbridge.crosses.borrow_mut().push(Rc::downgrade(&new_bridge_rc));
-                                }
+                if let Some((above, _)) = puzzle.cast(pos, Direction::N, is_node) {
+                    let oldy_node_rc = node_at[above].upgrade().unwrap();
+                    let new_bridge: BrRef = graph._add_bridge((Rc::downgrade(&oldy_node_rc), new_node.clone()));
+                    let new_bridge_rc = new_bridge.upgrade().unwrap();
+                    let new_node_rc = new_node.upgrade().unwrap();
+
+                    vert_bridges[x].push(new_bridge_rc.clone());
+
+                    // Check if bridge crosses any previous bridges
+                    for span_y in (oldy_node_rc.coords.1 + 1)..new_node_rc.coords.1 {
+                        for hbridge in horz_bridges.get(span_y as usize).unwrap() {
+                            let (ref n1, ref n2) = hbridge.nodes;
+                            let (x1, x2) = (n1.upgrade().unwrap().coords.0, n2.upgrade().unwrap().coords.0);
+                            let (xa, xb) = if x1 < x2 {(x1, x2)} else {(x2, x1)};
+                            if xa < (x as u32) && (x as u32) < xb {
+                                new_bridge_rc.crosses.borrow_mut().push(Rc::downgrade(hbridge));
+                                hbridge.crosses.borrow_mut().push(Rc::downgrade(&new_bridge_rc));
                             }
                         }
-
-                        *new_node_rc.up.borrow_mut() = new_bridge.clone();
-                        *oldy_node_rc.dn.borrow_mut() = new_bridge.clone();
                     }
-                    // debug("past oldys");
 
-                    oldx = new_node.clone();
-                    oldys[x] = new_node.clone();
-                    // debug(&format!("oldys len:{}", oldys.len()));
+                    *new_node_rc.up.borrow_mut() = new_bridge.clone();
+                    *oldy_node_rc.dn.borrow_mut() = new_bridge.clone();
                 }
             }
         }
 
-        graph.node_queue = graph.nodes.iter().rev().map(|ndrc| Rc::downgrade(ndrc)).collect();
+        // the islands can only say which bridges lead out of them once all the bridges are in
+        graph.islands = graph.nodes.iter()
+            .map(|ndrc| IslandSlot::Isl(Island::from_node(Rc::downgrade(ndrc))))
+            .collect();
+        graph.node_queue = graph.nodes.iter().rev().map(Rc::downgrade).collect();
 
         graph
     }
 
     fn _add_node(&mut self, coords: (u32, u32), quota: u32) -> NdRef {
         let node_id = self.nodes.len();
-        let new_node = Rc::new(Node::new(self, coords, quota, node_id));
+        let new_node = Rc::new(Node::new(coords, quota, node_id));
         self._add_island_for(Rc::downgrade(&new_node));
         let return_ref = Rc::downgrade(&new_node);
         self.nodes.push(new_node);
@@ -224,7 +176,7 @@ impl HashiGraph {
 
     fn _add_bridge(&mut self, nodes: (NdRef, NdRef)) -> BrRef {
         let bridge_id = self.bridges.len();
-        let new_bridge = Rc::new(Bridge::new(self, nodes, bridge_id));
+        let new_bridge = Rc::new(Bridge::new(nodes, bridge_id));
         let return_ref = Rc::downgrade(&new_bridge);
         self.bridges.push(new_bridge);
         return_ref
@@ -234,7 +186,7 @@ impl HashiGraph {
         self.islands.push(IslandSlot::Isl(Island::from_node(node)));
     }
 
-    fn _get_island(&self, node_id: usize) -> &Island {
+    fn _island_index(&self, node_id: usize) -> usize {
         let mut index = node_id;
         let mut visited = vec![false; self.islands.len()];
         loop {
@@ -244,26 +196,40 @@ impl HashiGraph {
                     if visited[new_index] {panic!("Infinite loop!")}
                     index = new_index;
                 },
-                IslandSlot::Isl(ref isl) => return isl,
+                IslandSlot::Isl(_) => return index,
             }
         }
     }
 
-    fn _merge_islands(&mut self, i1: &Island, i2: &Island) {
-        let (minid, maxid) = if i1.id < i2.id {(i1.id, i2.id)} else {(i2.id, i1.id)};
-        let new_island = Island {
-            nodes: i1.nodes.union(&i2.nodes).cloned().collect(),
-            ext_bridges: i1.ext_bridges.intersection(&i2.ext_bridges).cloned().collect(),
-            id: minid,
-        };
-        self.islands[minid] = IslandSlot::Isl(new_island);
-        self.islands[maxid] = IslandSlot::IslPtr(minid);
+    fn _get_island(&self, node_id: usize) -> &Island {
+        match self.islands[self._island_index(node_id)] {
+            IslandSlot::Isl(ref isl) => isl,
+            IslandSlot::IslPtr(_) => unreachable!(),
+        }
+    }
+
+    fn _get_island_mut(&mut self, node_id: usize) -> &mut Island {
+        let index = self._island_index(node_id);
+        match self.islands[index] {
+            IslandSlot::Isl(ref mut isl) => isl,
+            IslandSlot::IslPtr(_) => unreachable!(),
+        }
+    }
+
+    fn _merge_islands(&mut self, id1: ID, id2: ID) {
+        let (minid, maxid) = if id1 < id2 {(id1, id2)} else {(id2, id1)};
+        let old_slot = mem::replace(&mut self.islands[maxid], IslandSlot::IslPtr(minid));
+        if let (IslandSlot::Isl(ref mut kept), IslandSlot::Isl(merged)) = (&mut self.islands[minid], old_slot) {
+            kept.nodes.extend(merged.nodes);
+            // bridges between the two lead out of neither any more
+            kept.ext_bridges = &kept.ext_bridges ^ &merged.ext_bridges;
+        }
     }
 
     fn _num_islands(&self) -> usize {
-        self.islands.iter().filter(|slot| match slot {
-            &&IslandSlot::Isl(_) => true,
-            &&IslandSlot::IslPtr(_) => false
+        self.islands.iter().filter(|slot| match **slot {
+            IslandSlot::Isl(_) => true,
+            IslandSlot::IslPtr(_) => false
         }).count()
     }
 
@@ -275,30 +241,65 @@ impl HashiGraph {
         self.nodes[id].clone()
     }
 
+    // Follows up a change to `bridge`: its nodes go back on the queue to have their quotas
+    //  reapplied, a linked bridge closes the bridges crossing it and joins its nodes' islands,
+    //  and a bridge with no slots left either way no longer leads out of its islands.
+    fn propagate(&mut self, bridge: &Rc<Bridge>) {
+        let (ref n1, ref n2) = bridge.nodes;
+        let (id1, id2) = (n1.upgrade().unwrap().id, n2.upgrade().unwrap().id);
+        self.node_queue.push(n1.clone());
+        self.node_queue.push(n2.clone());
+
+        if bridge.num_linked_slots() > 0 {
+            let crossing = bridge.crosses.borrow().iter()
+                .filter_map(|br| br.upgrade())
+                .filter(|br| br.num_open_slots() > 0)
+                .collect::<Vec<_>>();
+            for cross in crossing {
+                cross.close_slots(cross.num_open_slots()).unwrap();
+                self.propagate(&cross);
+            }
+
+            let (isl1, isl2) = (self._get_island(id1).id, self._get_island(id2).id);
+            if isl1 != isl2 {
+                self._merge_islands(isl1, isl2);
+            }
+        } else if bridge.num_open_slots() == 0 {
+            self._get_island_mut(id1).ext_bridges.remove(&bridge.id);
+            self._get_island_mut(id2).ext_bridges.remove(&bridge.id);
+        }
+    }
+
     fn solve(&mut self) {
         // unimplemented!();
         'solve: loop {
             if let Some(curr_node) = self.node_queue.pop() {
-                curr_node.upgrade().unwrap().apply_quota_rules();
+                for bridge in curr_node.upgrade().unwrap().apply_quota_rules() {
+                    self.propagate(&bridge);
+                }
                 continue 'solve
             }
             // Sole External Bridge
             if self._num_islands() > 1 {
-                for island_slot in &self.islands {
-                    if let &IslandSlot::Isl(ref island) = island_slot {
-                        if island.ext_bridges.len() == 1 {
-                            let &br_id = island.ext_bridges.iter().next().unwrap();
-                            self.get_bridge_from_id(br_id).link_slots(1);
-                            continue 'solve
-                        }
-                    }
+                let sole_bridge = self.islands.iter()
+                    .filter_map(|slot| match *slot {
+                        IslandSlot::Isl(ref isl) if isl.ext_bridges.len() == 1 => isl.ext_bridges.iter().next(),
+                        _ => None
+                    })
+                    .next()
+                    .map(|&br_id| self.get_bridge_from_id(br_id));
+                if let Some(bridge) = sole_bridge {
+                    debug!("sole external bridge: linking {}", bridge);
+                    bridge.link_slots(1).unwrap();
+                    self.propagate(&bridge);
+                    continue 'solve
                 }
             }
             // Between Islands
             if self._num_islands() > 2 {
                 let islands = self.islands.iter()
                     .filter_map(|slot| match slot {
-                        &IslandSlot::Isl(ref isl) => Some(isl),
+                        IslandSlot::Isl(isl) => Some(isl),
                         &IslandSlot::IslPtr(_) => None
                     })
                     .collect::<Vec<_>>();
@@ -329,9 +330,12 @@ impl HashiGraph {
                     if lonely_ext_node_ids.contains(&n1.id) &&
                        lonely_ext_node_ids.contains(&n2.id) &&
                        n1.rem_quota() == n2.rem_quota() {
-                        let num_to_close = ext_bridge.num_open_slots() - n1.rem_quota() + 1;
+                        // filling both quotas from this bridge would cut the pair off from the rest
+                        let num_to_close = (ext_bridge.num_open_slots() + 1).saturating_sub(n1.rem_quota());
                         if num_to_close > 0 {
-                            ext_bridge.link_slots(num_to_close);
+                            debug!("between islands: closing {} slots of {}", num_to_close, ext_bridge);
+                            ext_bridge.close_slots(num_to_close).unwrap();
+                            self.propagate(&ext_bridge);
                             continue 'solve
                         }
                     }
@@ -341,7 +345,7 @@ impl HashiGraph {
             if self._num_islands() > 1 {
                 let islands = self.islands.iter()
                     .filter_map(|slot| match slot {
-                        &IslandSlot::Isl(ref isl) => Some(isl),
+                        IslandSlot::Isl(isl) => Some(isl),
                         &IslandSlot::IslPtr(_) => None
                     })
                     .collect::<Vec<_>>();
@@ -378,21 +382,23 @@ impl HashiGraph {
                     if let Some((Some(sh_br_id), _)) = shared_bridge {
                         let sh_br = self.get_bridge_from_id(sh_br_id);
                         let rem_quota = cmp::max(n1.rem_quota(), n2.rem_quota());
-                        let to_close = sh_br.num_open_slots() - rem_quota + 1;
+                        let to_close = (sh_br.num_open_slots() + 1).saturating_sub(rem_quota);
                         if to_close > 0 {
-                            sh_br.close_slots(to_close);
+                            debug!("within islands: closing {} slots of {}", to_close, sh_br);
+                            sh_br.close_slots(to_close).unwrap();
+                            self.propagate(&sh_br);
                             continue 'solve
                         }
                     }
                 }
             }
             // Screw it. Just guess.
-            for ref bridge in &self.bridges {
-                if bridge.num_open_slots() > 0 {
-                    bridge.link_slots(1);
-                    // println!("guess used");
-                    continue 'solve
-                }
+            let open_bridge = self.bridges.iter().find(|bridge| bridge.num_open_slots() > 0).cloned();
+            if let Some(bridge) = open_bridge {
+                debug!("guessing: linking {}", bridge);
+                bridge.link_slots(1).unwrap();
+                self.propagate(&bridge);
+                continue 'solve
             }
 
             break;
@@ -408,7 +414,6 @@ impl HashiGraph {
             if links > 0 {
                 // Two coordinates and one integer: a node, one of its neighbors, the number of links
                 //   connecting them.
-                // println!("0 0 2 0 1");
                 Some(format!("{} {} {} {} {}", x1, y1, x2, y2, links))
             } else {
                 None
@@ -425,11 +430,10 @@ impl HashiGraph {
 }
 
 impl Node {
-    fn new(graph: &mut HashiGraph, coords: (u32, u32), quota: u32, node_id: usize) -> Self {
+    fn new(coords: (u32, u32), quota: u32, node_id: usize) -> Self {
         Node {
-            graph: graph as *mut HashiGraph,
-            coords: coords,
-            quota: quota,
+            coords,
+            quota,
             id: node_id,
             up: RefCell::new(Weak::new()),
             dn: RefCell::new(Weak::new()),
@@ -438,6 +442,7 @@ impl Node {
         }
     }
 
+    #[allow(dead_code)]
     fn set_bridge(&self, bridge: BrRef, dir: &str) {
         match dir {
             "up" => *self.up.borrow_mut() = bridge,
@@ -448,6 +453,7 @@ impl Node {
         }
     }
 
+    #[allow(dead_code)]
     fn num_open_slots(&self) -> u32 {
         sum_bridges!(self, num_open_slots)
     }
@@ -467,35 +473,29 @@ impl Node {
     fn rem_close_quota(&self) -> u32 {
         self.close_quota() - self.num_closed_slots()
     }
-    fn apply_quota_rules(&self) {
-        // debug("begin apply_quota_rules");
-        // debug(&format!("quota: {}\n open: {}\n linked: {}\n closed: {}\n rem_quota: {}\n close_quota: {}\n rem_close_quota: {}",
-        //     self.quota,
-        //     self.num_open_slots(),
-        //     self.num_linked_slots(),
-        //     self.num_closed_slots(),
-        //     self.rem_quota(),
-        //     self.close_quota(),
-        //     self.rem_close_quota()
-        // ));
+    // Links the slots this node can't do without and closes the ones it can't use, returning
+    //  the bridges that changed.
+    fn apply_quota_rules(&self) -> Vec<Rc<Bridge>> {
+        trace!("applying quota rules to node {}: quota {}, open {}, linked {}, closed {}, rem_quota {}, rem_close_quota {}",
+               self.id, self.quota, self.num_open_slots(), self.num_linked_slots(), self.num_closed_slots(),
+               self.rem_quota(), self.rem_close_quota());
 
-        for mut bridge in culled_bridge_iter!(self) {
-            // debug(&format!("applying to {}", bridge));
+        let mut changed = Vec::new();
+        for bridge in culled_bridge_iter!(self) {
+            let open_before = bridge.num_open_slots();
 
             let disparity = bridge.num_open_slots().saturating_sub(self.rem_close_quota());
-            // debug(&format!("open_slots: {} - rem_close_quota: {} = linking {} slots",
-            //     bridge.num_open_slots(),
-            //     self.rem_close_quota(),
-            //     disparity));
             bridge.link_slots(disparity).unwrap();
 
             let disparity = bridge.num_open_slots().saturating_sub(self.rem_quota());
-            // debug(&format!("open_slots: {} - rem_quota: {} = closing {} slots",
-            //     bridge.num_open_slots(),
-            //     self.rem_quota(),
-            //     disparity));
             bridge.close_slots(disparity).unwrap();
+
+            if bridge.num_open_slots() != open_before {
+                trace!("quota rules changed {}", bridge);
+                changed.push(bridge);
+            }
         }
+        changed
     }
 }
 
@@ -506,13 +506,12 @@ impl Display for Node {
 }
 
 impl Bridge {
-    fn new(graph: &mut HashiGraph, nodes: (NdRef, NdRef), id: ID) -> Self {
+    fn new(nodes: (NdRef, NdRef), id: ID) -> Self {
         Bridge {
-            graph: graph as *mut HashiGraph,
-            nodes: nodes,
+            nodes,
             domain: RefCell::new(Domain::new()),
             crosses: RefCell::new(Vec::new()),
-            id: id,
+            id,
         }
     }
     fn num_slots(&self) -> u32 {
@@ -528,15 +527,12 @@ impl Bridge {
         self.domain.borrow().num_linked_slots()
     }
 
+    // The graph follows these up with `HashiGraph::propagate`.
     fn link_slots(&self, n: u32) -> Result<(), String> {
-        self.domain.borrow_mut().link_slots(n).unwrap();
-        // TODO: add nodes to queue, close crossed, merge islands
-        Ok(())
+        self.domain.borrow_mut().link_slots(n)
     }
     fn close_slots(&self, n: u32) -> Result<(), String> {
-        self.domain.borrow_mut().close_slots(n).unwrap();
-        // TODO add nodes to queue, unexternify bridge
-        Ok(())
+        self.domain.borrow_mut().close_slots(n)
     }
 }
 
@@ -569,14 +565,7 @@ impl HashiDomain for Domain {
 
     fn link_slots(&mut self, n: u32) -> Result<(), String> {
         let open_slots = self.num_open_slots();
-        // println!("before: {:?}", self);
-        // println!("n: {:?}", n);
-        // println!("open_slots: {:?}", open_slots);
         if n == 0 {
-            // debug(&format!(
-            //     "attempted to link 0 slots ({} slots open)",
-            //     open_slots
-            // ));
             return Ok(());
         }
         if open_slots == 0 {
@@ -591,21 +580,14 @@ impl HashiDomain for Domain {
         }
 
         let curr_min = self.iter().position(|&elem| elem).unwrap();
-        // debug(&format!("slice: [{}..{}]", curr_min, (curr_min + n as usize)));
         for elem in self[curr_min..(curr_min + n as usize)].iter_mut() {
             *elem = false;
         }
-        // println!("after: {:?}", self);
-        // println!("open slots after: {}", self.num_open_slots());
         Ok(())
     }
     fn close_slots(&mut self, n: u32) -> Result<(), String> {
         let open_slots = self.num_open_slots();
         if n == 0 {
-            // debug(&format!(
-            //     "attempted to close 0 slots ({} slots open)",
-            //     open_slots
-            // ));
             return Ok(());
         }
         if open_slots == 0 {
@@ -639,9 +621,9 @@ impl Island {
         let id = init_node.id;
 
         Island {
-            nodes: nodes,
-            ext_bridges: ext_bridges,
-            id: id,
+            nodes,
+            ext_bridges,
+            id,
         }
     }
 }
@@ -651,19 +633,18 @@ impl Island {
 * The machines are gaining ground. Time to show them what we're really made of...
 **/
 // Write an action using println!("message...");
-// To debug: print_err!("Debug message...");
+// To debug: debug!("Debug message...");
 fn main() {
+    let mut input = Input::stdin();
+
     // the number of cells on the X axis
-    let width = parse_line::<u32>().expect("parsing width failed");
+    let width = input.parse::<usize>();
 
     // the number of cells on the Y axis
-    let height = parse_line::<u32>().expect("parsing height failed");
+    let height = input.parse::<usize>();
 
-    let mut puzzle: Vec<String> = Vec::with_capacity(height as usize);
-    for _ in 0..height {
-        let line = read_line(); // width characters, each either a number or a '.'
-        puzzle.push(line);
-    }
+    // width characters, each either a number or a '.'
+    let puzzle = Grid::from_rows(input.grid(width, height)).unwrap_or_else(|e| panic!("{}", e));
 
     let mut graph = HashiGraph::new(&puzzle);
 
@@ -727,7 +708,40 @@ mod tests {
 
     #[test]
     fn test_bridge() {
-        unimplemented!()
+        let puzzle = Grid::from_lines(&CROSSING_PUZZLE.split("\n").collect::<Vec<_>>()).unwrap();
+        let mut graph = HashiGraph::new(&puzzle);
+        assert_eq!(graph.bridges.len(), 2);
+        graph.node_queue.clear();
+
+        let horz = graph.get_bridge_from_id(0);
+        let vert = graph.get_bridge_from_id(1);
+        assert_eq!(horz.to_string(), "N1-N2");
+        assert_eq!(vert.to_string(), "N0-N3");
+        assert!(Rc::ptr_eq(&horz.crosses.borrow()[0].upgrade().unwrap(), &vert));
+        assert_eq!(horz.num_slots(), 2);
+        assert_eq!(horz.num_open_slots(), 2);
+        assert!(graph._get_island(0).ext_bridges.contains(&1));
+
+        horz.link_slots(1).unwrap();
+        graph.propagate(&horz);
+        assert_eq!(horz.num_linked_slots(), 1);
+        assert_eq!(horz.num_open_slots(), 1);
+        // linking closes the bridge crossing it...
+        assert_eq!(vert.num_closed_slots(), 2);
+        assert_eq!(vert.num_open_slots(), 0);
+        // ...joins the islands at either end...
+        assert_eq!(graph._num_islands(), 3);
+        assert_eq!(graph._get_island(2).id, 1);
+        assert!(graph._get_island(1).ext_bridges.is_empty());
+        // ...and takes the closed bridge out of the islands it led from
+        assert!(graph._get_island(0).ext_bridges.is_empty());
+        assert!(graph._get_island(3).ext_bridges.is_empty());
+        let mut queued = graph.node_queue.iter().map(|nd| nd.upgrade().unwrap().id).collect::<Vec<_>>();
+        queued.sort();
+        assert_eq!(queued, vec![0, 1, 2, 3]);
+
+        assert_eq!(horz.link_slots(2), Err("attempted to link 2 slots in bridge with 1 open slots".to_string()));
+        assert_eq!(vert.close_slots(1), Err("attempted to close 1 slots in a full bridge".to_string()));
     }
 
     #[test]
@@ -788,7 +802,7 @@ mod tests {
 
     #[test]
     fn test_hashigraph() {
-        let puzzle = INTERMEDIATE_1_PUZZLE.split("\n").collect::<Vec<_>>();
+        let puzzle = Grid::from_lines(&INTERMEDIATE_1_PUZZLE.split("\n").collect::<Vec<_>>()).unwrap();
         let mut graph = HashiGraph::new(&puzzle);
         // TODO test that it initialized properly, e.g. all the proper nodes, bridges, & islands
         assert_eq!(graph.nodes.len(), 10);
@@ -823,6 +837,11 @@ mod tests {
 ..5.4
 332..";
 
+    const CROSSING_PUZZLE: &str = "\
+.1.
+2.2
+.1.";
+
     #[allow(dead_code)]
     const MULTIPLE_SOLUTIONS_PUZZLE: &str = "\
 3.3
@@ -0,0 +1,46 @@
+// Runs the one-shot solutions on every tests/<puzzle>/NN.in and checks what they print against
+//  the matching NN.out.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use harness::expected::{self, Order, Verdict};
+
+// A debug build in a busy test run needs more than CodinGame's second.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn check(puzzle: &str, command: &mut Command, order: Order) {
+    let cases = expected::cases(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(puzzle)).unwrap();
+    assert!(!cases.is_empty());
+    for case in cases {
+        let run = expected::run(&case, command, order, TIMEOUT).unwrap();
+        assert_eq!(run.verdict, Verdict::Passed, "{} case {}\n{}", puzzle, case.name, run.stderr);
+    }
+}
+
+#[test]
+fn test_there_is_no_spoon_ep1() {
+    check("there_is_no_spoon_ep1", &mut Command::new(env!("CARGO_BIN_EXE_there_is_no_spoon_ep1")), Order::Exact);
+}
+
+#[test]
+fn test_there_is_no_spoon_ep2() {
+    check("there_is_no_spoon_ep2", &mut Command::new(env!("CARGO_BIN_EXE_there_is_no_spoon_ep2")), Order::Any);
+}
+
+#[test]
+fn test_monday_tuesday_happy_days() {
+    check("monday_tuesday_happy_days", &mut Command::new(env!("CARGO_BIN_EXE_monday_tuesday_happy_days")),
+          Order::Exact);
+}
+
+#[test]
+fn test_game_of_life() {
+    check("game_of_life", &mut Command::new(env!("CARGO_BIN_EXE_game_of_life")), Order::Exact);
+}
+
+#[test]
+fn test_ascii_art() {
+    check("ascii_art", &mut Command::new(env!("CARGO_BIN_EXE_ascii_art")), Order::Exact);
+}
@@ -0,0 +1,72 @@
+// Plays the turn-based solutions through every level in tests/levels against their referees.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use harness::scripted::ScriptedReferee;
+use harness::skynet::SkynetReferee;
+use harness::thor::{GiantsReferee, ThorReferee};
+use harness::{play, Limits, Outcome, Referee};
+
+// The usual limits would do in a release build, but a debug build in a busy test run can be
+//  slow to answer.
+fn limits() -> Limits {
+    Limits { first_turn: Duration::from_secs(5), turn: Duration::from_secs(1), ..Limits::default() }
+}
+
+fn levels(game: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/levels").join(game);
+    let mut levels = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect::<Vec<_>>();
+    levels.sort();
+    assert!(!levels.is_empty());
+    levels
+}
+
+// Plays `command` through each level of `game`, insisting on a win.
+fn check<F>(game: &str, referee: F, command: &mut Command)
+    where F: Fn(&str) -> Result<Box<dyn Referee>, String>
+{
+    for level in levels(game) {
+        let mut referee = referee(&fs::read_to_string(&level).unwrap()).unwrap();
+        let report = play(referee.as_mut(), command, &limits()).unwrap();
+        assert_eq!(report.outcome, Outcome::Won, "{}: {}\n{}", level.display(), report, report.stderr);
+    }
+}
+
+fn skynet(text: &str) -> Result<Box<dyn Referee>, String> {
+    SkynetReferee::parse(text).map(|r| Box::new(r) as Box<dyn Referee>)
+}
+
+#[test]
+fn test_skynet_ep1() {
+    check("skynet_ep1", skynet, &mut Command::new(env!("CARGO_BIN_EXE_skynet_revolution_ep1")));
+}
+
+#[test]
+fn test_skynet_ep2() {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_skynet_revolution_ep2"));
+    check("skynet_ep1", skynet, &mut command);
+    check("skynet_ep2", skynet, &mut command);
+}
+
+#[test]
+fn test_power_of_thor_ep1() {
+    check("power_of_thor_ep1", |text| ThorReferee::parse(text).map(|r| Box::new(r) as Box<dyn Referee>),
+          &mut Command::new(env!("CARGO_BIN_EXE_power_of_thor_ep1")));
+}
+
+#[test]
+fn test_power_of_thor_ep2() {
+    check("power_of_thor_ep2", |text| GiantsReferee::parse(text).map(|r| Box::new(r) as Box<dyn Referee>),
+          &mut Command::new(env!("CARGO_BIN_EXE_power_of_thor_ep2")));
+}
+
+#[test]
+fn test_reverse_engineering() {
+    let mut command = Command::new("python3");
+    command.arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("reverse engineering.py"));
+    check("reverse_engineering", |text| ScriptedReferee::parse(text).map(|r| Box::new(r) as Box<dyn Referee>),
+          &mut command);
+}